@@ -0,0 +1,828 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use anyhow::{Result, Context, bail};
+use crate::editors::{Delivery, RuleMetadata, RuleOverrides};
+use crate::json_config;
+use crate::managed::ManagedFile;
+use crate::manifest::SkillManifest;
+use crate::store::BlobStore;
+
+/// Where Claude Code skill references were written before native skill directories
+const LEGACY_CLAUDE_CONFIG: &str = ".claude/config";
+
+/// File the VS Code memory context is written to (referenced from settings.json)
+const VSCODE_MEMORY_FILE: &str = ".vscode/skillctl-memory.md";
+
+/// Cursor rule carrying the memory context, apart from the built-in `memory` skill's rule
+const CURSOR_MEMORY_RULE: &str = "skillctl-memory.mdc";
+
+/// Start of the memory rule, which older versions wrote to `.cursor/rules/memory.mdc`
+const CURSOR_MEMORY_HEADER: &str = "---\ndescription: Global Active Memory\n";
+
+/// Name substituted into a `files` adapter target for the memory context
+const CUSTOM_MEMORY_NAME: &str = "skillctl-memory";
+
+/// How skillctl talks to one editor: where it looks for rules and how skills
+/// and memory are written there. Adding an editor means adding one implementation.
+///
+/// The default methods write into the skillctl-managed block of `config_file()`.
+pub trait EditorAdapter {
+    /// Editor name as written in skills.json
+    fn name(&self) -> &str;
+
+    /// Directory whose presence means the editor is used in the project
+    fn config_dir(&self) -> PathBuf;
+
+    /// Rules file the editor reads
+    fn config_file(&self) -> PathBuf;
+
+    /// Directory copied or symlinked skills are placed in
+    fn skills_dir(&self) -> PathBuf;
+
+    fn default_delivery(&self) -> Delivery {
+        Delivery::Reference
+    }
+
+    /// Initial content of a freshly created config file
+    fn initial_config(&self) -> String {
+        format!("# AI Rules for {}\n", self.name())
+    }
+
+    fn detect(&self) -> bool {
+        self.config_dir().exists()
+    }
+
+    /// Text pointing the editor at a skill, placed in the managed block
+    fn render_reference(&self, skill_name: &str, skill_path: &str) -> String {
+        format!("- Skill ({}) -> Read file: {}", skill_name, skill_path)
+    }
+
+    /// Point the editor at a skill's SKILL.md in the store
+    fn inject_skill(&self, skill_name: &str, skill_path: &Path, _overrides: Option<&RuleOverrides>) -> Result<()> {
+        let reference = self.render_reference(skill_name, &skill_path.to_string_lossy());
+        set_managed_skill(&self.config_file(), skill_name, &reference)
+    }
+
+    /// Remove the pointer to a skill
+    fn remove_skill(&self, skill_name: &str) -> Result<()> {
+        remove_managed_skill(&self.config_file(), skill_name)
+    }
+
+    /// Skills the editor config points at, as written by skillctl
+    fn managed_references(&self) -> Result<Vec<String>> {
+        let config_file = self.config_file();
+        if !config_file.exists() {
+            return Ok(Vec::new());
+        }
+        Ok(read_managed_file(&config_file)?.skill_names())
+    }
+
+    /// Replace the memory context shown to the editor
+    fn inject_memory(&self, memory_content: &str) -> Result<()> {
+        set_managed_memory(&self.config_file(), memory_content)
+    }
+}
+
+// ============================================================================
+// BUILT-IN EDITORS
+// ============================================================================
+
+/// Cursor: one `.cursor/rules/<skill>.mdc` rule per skill (`~/.cursor/rules` for user rules)
+#[derive(Default)]
+pub struct CursorAdapter {
+    /// Home directory for user-level rules, `None` for the project
+    pub home: Option<PathBuf>,
+}
+
+impl CursorAdapter {
+    fn rules_dir(&self) -> PathBuf {
+        under(&self.home, ".cursor/rules")
+    }
+}
+
+impl EditorAdapter for CursorAdapter {
+    fn name(&self) -> &str { "Cursor" }
+    fn config_dir(&self) -> PathBuf { under(&self.home, ".cursor") }
+    fn config_file(&self) -> PathBuf { under(&self.home, ".cursorrules") }
+    fn skills_dir(&self) -> PathBuf { under(&self.home, ".cursor/skills") }
+
+    fn inject_skill(&self, skill_name: &str, skill_path: &Path, overrides: Option<&RuleOverrides>) -> Result<()> {
+        let rules_dir = self.rules_dir();
+        fs::create_dir_all(&rules_dir)
+            .context("Failed to create .cursor/rules directory")?;
+
+        // Describe the rule with the skill's own frontmatter, then project overrides
+        let manifest = read_manifest(skill_path);
+        let metadata = RuleMetadata::resolve(skill_name, manifest.as_ref(), overrides);
+
+        let rule_file = rules_dir.join(format!("{}.mdc", skill_name));
+        let content = format!(
+            "---\n{}---\n# {}\n\nRead logic from: {}\n",
+            metadata.to_frontmatter()?,
+            skill_name,
+            skill_path.to_string_lossy()
+        );
+        fs::write(&rule_file, content)
+            .context("Failed to write Cursor rule file")
+    }
+
+    fn remove_skill(&self, skill_name: &str) -> Result<()> {
+        let rule_file = self.rules_dir().join(format!("{}.mdc", skill_name));
+        if rule_file.exists() {
+            fs::remove_file(rule_file)
+                .context("Failed to remove Cursor rule file")?;
+        }
+        Ok(())
+    }
+
+    fn managed_references(&self) -> Result<Vec<String>> {
+        let mut names = Vec::new();
+        let rules_dir = self.rules_dir();
+        if !rules_dir.exists() {
+            return Ok(names);
+        }
+
+        for dir_entry in fs::read_dir(&rules_dir).context("Failed to read .cursor/rules directory")? {
+            let path = dir_entry?.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("mdc") || path.ends_with(CURSOR_MEMORY_RULE) {
+                continue;
+            }
+            // Only count rule files generated by inject_skill
+            let content = fs::read_to_string(&path).unwrap_or_default();
+            if content.starts_with("---\n") && content.contains("\nRead logic from: ") {
+                if let Some(stem) = path.file_stem().and_then(|s| s.to_str()) {
+                    names.push(stem.to_string());
+                }
+            }
+        }
+        names.sort();
+        Ok(names)
+    }
+
+    fn inject_memory(&self, memory_content: &str) -> Result<()> {
+        let rules_dir = self.rules_dir();
+        fs::create_dir_all(&rules_dir)
+            .context("Failed to create .cursor/rules directory")?;
+
+        // Older versions shared memory.mdc with the memory skill's rule
+        let legacy_file = rules_dir.join("memory.mdc");
+        if fs::read_to_string(&legacy_file).is_ok_and(|content| content.starts_with(CURSOR_MEMORY_HEADER)) {
+            fs::remove_file(&legacy_file)
+                .context("Failed to remove old Cursor memory file")?;
+        }
+
+        let content = format!("{}globs: \"*\"\n---\n{}", CURSOR_MEMORY_HEADER, memory_content);
+        fs::write(rules_dir.join(CURSOR_MEMORY_RULE), content)
+            .context("Failed to write Cursor memory file")
+    }
+}
+
+/// Windsurf: `.windsurfrules`, or the global rules under `~/.codeium/windsurf`
+#[derive(Default)]
+pub struct WindsurfAdapter {
+    /// Home directory for the global rules, `None` for the project
+    pub home: Option<PathBuf>,
+}
+
+impl EditorAdapter for WindsurfAdapter {
+    fn name(&self) -> &str { "Windsurf" }
+
+    fn config_dir(&self) -> PathBuf {
+        match &self.home {
+            Some(home) => home.join(".codeium/windsurf"),
+            None => PathBuf::from(".windsurf"),
+        }
+    }
+
+    fn config_file(&self) -> PathBuf {
+        match &self.home {
+            Some(home) => home.join(".codeium/windsurf/memories/global_rules.md"),
+            None => PathBuf::from(".windsurfrules"),
+        }
+    }
+
+    fn skills_dir(&self) -> PathBuf {
+        self.config_dir().join("skills")
+    }
+}
+
+/// Antigravity: skills in `.agent/rules.md`, memory in its own `.agent/memory.md`
+pub struct AntigravityAdapter;
+
+impl EditorAdapter for AntigravityAdapter {
+    fn name(&self) -> &str { "Antigravity" }
+    fn config_dir(&self) -> PathBuf { PathBuf::from(".agent") }
+    fn config_file(&self) -> PathBuf { PathBuf::from(".agent/rules.md") }
+    fn skills_dir(&self) -> PathBuf { PathBuf::from(".agent/skills") }
+
+    fn render_reference(&self, skill_name: &str, skill_path: &str) -> String {
+        format!("### Skill: {}\nRefer to logic in: `{}`", skill_name, skill_path)
+    }
+
+    fn inject_memory(&self, memory_content: &str) -> Result<()> {
+        let agent_dir = self.config_dir();
+        fs::create_dir_all(&agent_dir)
+            .context("Failed to create .agent directory")?;
+        fs::write(agent_dir.join("memory.md"), memory_content)
+            .context("Failed to write Antigravity memory file")
+    }
+}
+
+/// Claude Code: skills live in `.claude/skills/<name>/`, memory in `CLAUDE.md`
+/// (`~/.claude/skills` and `~/.claude/CLAUDE.md` at user level)
+#[derive(Default)]
+pub struct ClaudeCodeAdapter {
+    /// Home directory for user-level skills, `None` for the project
+    pub home: Option<PathBuf>,
+}
+
+impl EditorAdapter for ClaudeCodeAdapter {
+    fn name(&self) -> &str { "ClaudeCode" }
+    fn config_dir(&self) -> PathBuf { under(&self.home, ".claude") }
+    fn skills_dir(&self) -> PathBuf { under(&self.home, ".claude/skills") }
+
+    fn config_file(&self) -> PathBuf {
+        match &self.home {
+            Some(home) => home.join(".claude/CLAUDE.md"),
+            None => PathBuf::from("CLAUDE.md"),
+        }
+    }
+
+    fn default_delivery(&self) -> Delivery {
+        Delivery::Symlink
+    }
+
+    fn remove_skill(&self, skill_name: &str) -> Result<()> {
+        remove_managed_skill(&self.config_file(), skill_name)?;
+        // Drop references left in .claude/config by older versions of skillctl
+        if self.home.is_none() {
+            remove_managed_skill(Path::new(LEGACY_CLAUDE_CONFIG), skill_name)?;
+        }
+        Ok(())
+    }
+}
+
+pub struct ClineAdapter;
+
+impl EditorAdapter for ClineAdapter {
+    fn name(&self) -> &str { "Cline" }
+    fn config_dir(&self) -> PathBuf { PathBuf::from(".cline") }
+    fn config_file(&self) -> PathBuf { PathBuf::from(".cline/config") }
+    fn skills_dir(&self) -> PathBuf { PathBuf::from(".cline/skills") }
+
+    fn render_reference(&self, skill_name: &str, skill_path: &str) -> String {
+        format!("Running context for {}: See {}", skill_name, skill_path)
+    }
+}
+
+pub struct RooAdapter;
+
+impl EditorAdapter for RooAdapter {
+    fn name(&self) -> &str { "Roo" }
+    fn config_dir(&self) -> PathBuf { PathBuf::from(".roo") }
+    fn config_file(&self) -> PathBuf { PathBuf::from(".roo/config") }
+    fn skills_dir(&self) -> PathBuf { PathBuf::from(".roo/skills") }
+
+    fn render_reference(&self, skill_name: &str, skill_path: &str) -> String {
+        format!("Running context for {}: See {}", skill_name, skill_path)
+    }
+}
+
+pub struct OpenHandsAdapter;
+
+impl EditorAdapter for OpenHandsAdapter {
+    fn name(&self) -> &str { "OpenHands" }
+    fn config_dir(&self) -> PathBuf { PathBuf::from(".openhands") }
+    fn config_file(&self) -> PathBuf { PathBuf::from(".openhands/config") }
+    fn skills_dir(&self) -> PathBuf { PathBuf::from(".openhands/skills") }
+}
+
+pub struct TraeAdapter;
+
+impl EditorAdapter for TraeAdapter {
+    fn name(&self) -> &str { "Trae" }
+    fn config_dir(&self) -> PathBuf { PathBuf::from(".trae") }
+    fn config_file(&self) -> PathBuf { PathBuf::from(".trae/config") }
+    fn skills_dir(&self) -> PathBuf { PathBuf::from(".trae/skills") }
+}
+
+pub struct CopilotAdapter;
+
+impl EditorAdapter for CopilotAdapter {
+    fn name(&self) -> &str { "GitHub Copilot" }
+    fn config_dir(&self) -> PathBuf { PathBuf::from(".github") }
+    fn config_file(&self) -> PathBuf { PathBuf::from(".github/copilot-instructions.md") }
+    fn skills_dir(&self) -> PathBuf { PathBuf::from(".github/skills") }
+}
+
+/// Continue: `rules` entries in `.continue/config.json` (`~/.continue` at user level)
+#[derive(Default)]
+pub struct ContinueAdapter {
+    /// Home directory for the user-level config, `None` for the project
+    pub home: Option<PathBuf>,
+}
+
+impl EditorAdapter for ContinueAdapter {
+    fn name(&self) -> &str { "Continue" }
+    fn config_dir(&self) -> PathBuf { under(&self.home, ".continue") }
+    fn config_file(&self) -> PathBuf { under(&self.home, ".continue/config.json") }
+    fn skills_dir(&self) -> PathBuf { under(&self.home, ".continue/skills") }
+
+    fn initial_config(&self) -> String {
+        "{}\n".to_string()
+    }
+
+    fn inject_skill(&self, skill_name: &str, skill_path: &Path, _overrides: Option<&RuleOverrides>) -> Result<()> {
+        let skill_path = skill_path.to_string_lossy();
+        update_json_config(&self.config_file(), |root| json_config::continue_set_skill(root, skill_name, &skill_path))
+    }
+
+    fn remove_skill(&self, skill_name: &str) -> Result<()> {
+        if !self.config_file().exists() {
+            return Ok(());
+        }
+        update_json_config(&self.config_file(), |root| json_config::continue_remove_skill(root, skill_name).map(|_| ()))
+    }
+
+    fn managed_references(&self) -> Result<Vec<String>> {
+        Ok(json_config::continue_skill_names(&read_json_config(&self.config_file())?))
+    }
+
+    fn inject_memory(&self, memory_content: &str) -> Result<()> {
+        if !self.config_file().exists() && memory_content.trim().is_empty() {
+            return Ok(());
+        }
+        update_json_config(&self.config_file(), |root| json_config::continue_set_memory(root, memory_content))
+    }
+}
+
+/// VS Code: Copilot instruction files listed in `.vscode/settings.json`
+pub struct VSCodeAdapter;
+
+impl VSCodeAdapter {
+    /// Whether an instruction file entry points into a skill store or the
+    /// VS Code skills directory, rather than at a file of the user's
+    fn owns_instruction(&self, file: &str) -> bool {
+        let Some(skill_dir) = Path::new(file).parent() else {
+            return false;
+        };
+        skill_dir.starts_with(self.skills_dir())
+            || BlobStore::open_default().is_ok_and(|blobs| blobs.owns(skill_dir))
+    }
+}
+
+impl EditorAdapter for VSCodeAdapter {
+    fn name(&self) -> &str { "VSCode" }
+    fn config_dir(&self) -> PathBuf { PathBuf::from(".vscode") }
+    fn config_file(&self) -> PathBuf { PathBuf::from(".vscode/settings.json") }
+    fn skills_dir(&self) -> PathBuf { PathBuf::from(".vscode/skills") }
+
+    fn initial_config(&self) -> String {
+        "{}\n".to_string()
+    }
+
+    fn inject_skill(&self, skill_name: &str, skill_path: &Path, _overrides: Option<&RuleOverrides>) -> Result<()> {
+        let skill_path = skill_path.to_string_lossy();
+        update_json_config(&self.config_file(), |root| json_config::vscode_set_skill(root, skill_name, &skill_path, |f| self.owns_instruction(f)))
+    }
+
+    fn remove_skill(&self, skill_name: &str) -> Result<()> {
+        if !self.config_file().exists() {
+            return Ok(());
+        }
+        update_json_config(&self.config_file(), |root| json_config::vscode_remove_skill(root, skill_name, |f| self.owns_instruction(f)).map(|_| ()))
+    }
+
+    fn managed_references(&self) -> Result<Vec<String>> {
+        Ok(json_config::vscode_skill_names(&read_json_config(&self.config_file())?, |f| self.owns_instruction(f)))
+    }
+
+    fn inject_memory(&self, memory_content: &str) -> Result<()> {
+        if !self.config_file().exists() && memory_content.trim().is_empty() {
+            return Ok(());
+        }
+
+        // settings.json can only point at files, so the memory lives next to it
+        let has_memory = !memory_content.trim().is_empty();
+        let memory_file = Path::new(VSCODE_MEMORY_FILE);
+        if has_memory {
+            if let Some(parent) = memory_file.parent() {
+                fs::create_dir_all(parent)
+                    .context("Failed to create .vscode directory")?;
+            }
+            fs::write(memory_file, memory_content)
+                .context("Failed to write VS Code memory file")?;
+        } else if memory_file.exists() {
+            fs::remove_file(memory_file)
+                .context("Failed to remove VS Code memory file")?;
+        }
+        update_json_config(&self.config_file(), |root| json_config::vscode_set_file(root, VSCODE_MEMORY_FILE, has_memory))
+    }
+}
+
+// ============================================================================
+// USER-DEFINED EDITORS (skills.json `adapters`)
+// ============================================================================
+
+/// How a user-defined adapter lays out its target
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum CustomFormat {
+    /// One file; each skill's rendered template goes into the skillctl block
+    #[default]
+    Markdown,
+    /// One file per skill; `target` contains `{name}`
+    Files,
+}
+
+/// An editor declared in skills.json, for agents skillctl does not know about
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CustomAdapter {
+    /// Name used in messages and as the key in `delivery`
+    pub name: String,
+    /// File skillctl writes to (for `files`, a path containing `{name}`)
+    pub target: String,
+    /// Text written for each skill; `{name}`, `{path}` and `{description}` are substituted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
+    #[serde(default)]
+    pub format: CustomFormat,
+    /// Where copied or symlinked skills go (defaults to `skills/` next to the target)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub skills_dir: Option<String>,
+}
+
+impl CustomAdapter {
+    /// Reject names and paths that would let skills.json write outside the project
+    pub fn validate(&self) -> Result<()> {
+        if self.name.trim().is_empty() {
+            bail!("Custom adapter has an empty name");
+        }
+
+        let paths = std::iter::once(&self.target).chain(self.skills_dir.as_ref());
+        for path in paths {
+            let is_relative = Path::new(path).components()
+                .all(|c| matches!(c, Component::Normal(_) | Component::CurDir));
+            if path.trim().is_empty() || !is_relative {
+                bail!("Adapter '{}': '{}' must be a relative path inside the project", self.name, path);
+            }
+        }
+
+        let file_name = Path::new(&self.target).file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or_default();
+        match self.format {
+            CustomFormat::Files if !file_name.contains("{name}") => {
+                bail!("Adapter '{}': a 'files' target must contain {{name}} in its file name", self.name)
+            }
+            CustomFormat::Markdown if self.target.contains("{name}") => {
+                bail!("Adapter '{}': {{name}} is only allowed in 'files' targets", self.name)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn render(&self, skill_name: &str, skill_path: &Path) -> String {
+        let default_template = match self.format {
+            CustomFormat::Markdown => "Read and follow the skill instructions in {path}",
+            CustomFormat::Files => "# {name}\n\n{description}\n\nRead and follow the skill instructions in {path}\n",
+        };
+        let description = RuleMetadata::resolve(skill_name, read_manifest(skill_path).as_ref(), None).description;
+
+        self.template.as_deref()
+            .unwrap_or(default_template)
+            .replace("{name}", skill_name)
+            .replace("{path}", &skill_path.to_string_lossy())
+            .replace("{description}", &description)
+    }
+
+    /// Target file of one skill in the `files` format
+    fn skill_file(&self, skill_name: &str) -> PathBuf {
+        PathBuf::from(self.target.replace("{name}", skill_name))
+    }
+
+    /// Whether `path` was written by inject_skill for `skill_name`
+    fn owns_file(&self, path: &Path, skill_name: &str) -> bool {
+        fs::read_to_string(path).ok()
+            .and_then(|content| ManagedFile::file_tag(&content))
+            .is_some_and(|name| name == skill_name)
+    }
+}
+
+impl EditorAdapter for CustomAdapter {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn config_dir(&self) -> PathBuf {
+        Path::new(&self.target).parent()
+            .filter(|p| !p.as_os_str().is_empty())
+            .map(Path::to_path_buf)
+            .unwrap_or_else(|| PathBuf::from("."))
+    }
+
+    fn config_file(&self) -> PathBuf {
+        PathBuf::from(&self.target)
+    }
+
+    fn skills_dir(&self) -> PathBuf {
+        match &self.skills_dir {
+            Some(dir) => PathBuf::from(dir),
+            None => self.config_dir().join("skills"),
+        }
+    }
+
+    fn inject_skill(&self, skill_name: &str, skill_path: &Path, _overrides: Option<&RuleOverrides>) -> Result<()> {
+        let content = self.render(skill_name, skill_path);
+        match self.format {
+            CustomFormat::Markdown => {
+                let reference = ManagedFile::tagged_reference(skill_name, &content);
+                set_managed_skill(&self.config_file(), skill_name, &reference)
+            }
+            CustomFormat::Files => {
+                let skill_file = self.skill_file(skill_name);
+                create_parent_dir(&skill_file)?;
+                fs::write(&skill_file, ManagedFile::tagged_file(skill_name, &content))
+                    .with_context(|| format!("Failed to write {}", skill_file.display()))
+            }
+        }
+    }
+
+    fn remove_skill(&self, skill_name: &str) -> Result<()> {
+        match self.format {
+            CustomFormat::Markdown => remove_managed_skill(&self.config_file(), skill_name),
+            CustomFormat::Files => {
+                let skill_file = self.skill_file(skill_name);
+                if self.owns_file(&skill_file, skill_name) {
+                    fs::remove_file(&skill_file)
+                        .with_context(|| format!("Failed to remove {}", skill_file.display()))?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    fn managed_references(&self) -> Result<Vec<String>> {
+        if self.format == CustomFormat::Markdown {
+            let config_file = self.config_file();
+            if !config_file.exists() {
+                return Ok(Vec::new());
+            }
+            return Ok(read_managed_file(&config_file)?.skill_names());
+        }
+
+        // Files matching the target pattern belong to skillctl only if tagged by inject_skill
+        let pattern = self.config_file();
+        let file_pattern = pattern.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+        let (prefix, suffix) = file_pattern.split_once("{name}").unwrap_or((file_pattern, ""));
+        let dir = self.config_dir();
+
+        let mut names = Vec::new();
+        if !dir.exists() {
+            return Ok(names);
+        }
+        for dir_entry in fs::read_dir(&dir).with_context(|| format!("Failed to read {}", dir.display()))? {
+            let dir_entry = dir_entry?;
+            let file_name = dir_entry.file_name();
+            let name = file_name.to_str()
+                .and_then(|n| n.strip_prefix(prefix))
+                .and_then(|n| n.strip_suffix(suffix))
+                .filter(|n| !n.is_empty() && *n != CUSTOM_MEMORY_NAME);
+            if let Some(name) = name.filter(|n| self.owns_file(&dir_entry.path(), n)) {
+                names.push(name.to_string());
+            }
+        }
+        names.sort();
+        Ok(names)
+    }
+
+    fn inject_memory(&self, memory_content: &str) -> Result<()> {
+        match self.format {
+            CustomFormat::Markdown => set_managed_memory(&self.config_file(), memory_content),
+            CustomFormat::Files => {
+                let memory_file = self.skill_file(CUSTOM_MEMORY_NAME);
+                if memory_content.trim().is_empty() {
+                    if memory_file.exists() {
+                        fs::remove_file(&memory_file)
+                            .context("Failed to remove memory file")?;
+                    }
+                    return Ok(());
+                }
+                create_parent_dir(&memory_file)?;
+                fs::write(&memory_file, memory_content)
+                    .context("Failed to write memory file")
+            }
+        }
+    }
+}
+
+// ============================================================================
+// HELPERS
+// ============================================================================
+
+/// `path` inside the home directory for user-level adapters, relative otherwise
+fn under(home: &Option<PathBuf>, path: &str) -> PathBuf {
+    match home {
+        Some(home) => home.join(path),
+        None => PathBuf::from(path),
+    }
+}
+
+fn read_manifest(skill_path: &Path) -> Option<SkillManifest> {
+    fs::read_to_string(skill_path).ok()
+        .and_then(|content| SkillManifest::parse(&content).ok())
+}
+
+fn create_parent_dir(path: &Path) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        if !parent.as_os_str().is_empty() {
+            fs::create_dir_all(parent)
+                .context("Failed to create editor config directory")?;
+        }
+    }
+    Ok(())
+}
+
+fn set_managed_skill(config_file: &Path, skill_name: &str, reference: &str) -> Result<()> {
+    let mut managed = read_managed_file(config_file)?;
+    managed.set_skill(skill_name, reference);
+    write_managed_file(config_file, &managed)
+}
+
+/// Only the skillctl block is touched; user lines stay as they are
+fn remove_managed_skill(config_file: &Path, skill_name: &str) -> Result<()> {
+    if !config_file.exists() {
+        return Ok(());
+    }
+
+    let mut managed = read_managed_file(config_file)?;
+    if managed.remove_skill(skill_name) {
+        write_managed_file(config_file, &managed)?;
+    }
+    Ok(())
+}
+
+/// Replace the memory section of the skillctl block, leaving user content alone
+fn set_managed_memory(config_file: &Path, memory_content: &str) -> Result<()> {
+    // If config file doesn't exist, only create it if there is memory to write
+    if !config_file.exists() && memory_content.trim().is_empty() {
+        return Ok(());
+    }
+
+    let mut managed = read_managed_file(config_file)?;
+    managed.set_memory(memory_content);
+    write_managed_file(config_file, &managed)
+        .context("Failed to update editor config with memory")
+}
+
+/// Read a JSON/JSONC editor config file (a missing file reads as `{}`)
+fn read_json_config(config_file: &Path) -> Result<serde_json::Value> {
+    json_config::parse_jsonc(&read_json_text(config_file)?)
+        .with_context(|| format!("Failed to parse {}", config_file.display()))
+}
+
+fn read_json_text(config_file: &Path) -> Result<String> {
+    if !config_file.exists() {
+        return Ok(String::new());
+    }
+    fs::read_to_string(config_file)
+        .context("Failed to read editor config file")
+}
+
+/// Apply a change to a JSON editor config, preserving every other setting.
+/// In JSONC files only the changed settings are rewritten, keeping comments.
+fn update_json_config(config_file: &Path, change: impl FnOnce(&mut serde_json::Value) -> Result<()>) -> Result<()> {
+    let content = read_json_text(config_file)?;
+    let mut root = json_config::parse_jsonc(&content)
+        .with_context(|| format!("Failed to parse {}", config_file.display()))?;
+    change(&mut root)?;
+
+    let updated = json_config::update_jsonc(&content, &root)
+        .with_context(|| format!("Not rewriting {}", config_file.display()))?;
+    create_parent_dir(config_file)?;
+    fs::write(config_file, updated)
+        .context("Failed to write editor config file")
+}
+
+/// Read an editor config file and locate the block owned by skillctl
+fn read_managed_file(config_file: &Path) -> Result<ManagedFile> {
+    let content = if config_file.exists() {
+        fs::read_to_string(config_file)
+            .context("Failed to read editor config file")?
+    } else {
+        String::new()
+    };
+
+    ManagedFile::parse(&content)
+        .with_context(|| format!("Invalid skillctl block in {}", config_file.display()))
+}
+
+/// Write an editor config file back with an updated skillctl block
+fn write_managed_file(config_file: &Path, managed: &ManagedFile) -> Result<()> {
+    create_parent_dir(config_file)?;
+    fs::write(config_file, managed.render())
+        .context("Failed to write editor config file")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cursor_memory_keeps_memory_skill_rule() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let adapter = CursorAdapter { home: Some(temp_dir.path().to_path_buf()) };
+        let rules = temp_dir.path().join(".cursor/rules");
+
+        // A memory context written by an older version is moved out of the way
+        fs::create_dir_all(&rules).unwrap();
+        fs::write(rules.join("memory.mdc"), "---\ndescription: Global Active Memory\nglobs: *\n---\nold\n").unwrap();
+
+        adapter.inject_memory("- [ID: 1] [general] (Prio: 5) Uses Rust\n").unwrap();
+        assert!(!rules.join("memory.mdc").exists());
+
+        adapter.inject_skill("memory", Path::new("store/memory/SKILL.md"), None).unwrap();
+        adapter.inject_memory("- [ID: 1] [general] (Prio: 5) Uses Rust\n").unwrap();
+
+        // What verify checks: the memory skill is still referenced after a memory sync
+        assert_eq!(crate::editors::list_references(&adapter).unwrap(), vec!["memory"]);
+        assert!(fs::read_to_string(rules.join("memory.mdc")).unwrap().contains("Read logic from: store/memory/SKILL.md"));
+        assert!(fs::read_to_string(rules.join(CURSOR_MEMORY_RULE)).unwrap().contains("Uses Rust"));
+
+        // Re-linking the skill leaves the memory context alone
+        adapter.inject_skill("memory", Path::new("store/memory/SKILL.md"), None).unwrap();
+        assert!(fs::read_to_string(rules.join(CURSOR_MEMORY_RULE)).unwrap().contains("Uses Rust"));
+    }
+
+    #[test]
+    fn test_custom_adapter_validation() {
+        let adapter = |target: &str, format: CustomFormat| CustomAdapter {
+            name: "Acme".to_string(),
+            target: target.to_string(),
+            template: None,
+            format,
+            skills_dir: None,
+        };
+
+        assert!(adapter(".acme/rules.md", CustomFormat::Markdown).validate().is_ok());
+        assert!(adapter(".acme/rules/{name}.md", CustomFormat::Files).validate().is_ok());
+        assert!(adapter(".acme/rules.md", CustomFormat::Files).validate().is_err());
+        assert!(adapter(".acme/{name}.md", CustomFormat::Markdown).validate().is_err());
+        assert!(adapter("../outside.md", CustomFormat::Markdown).validate().is_err());
+        assert!(adapter("/etc/passwd", CustomFormat::Markdown).validate().is_err());
+
+        let files = adapter(".acme/rules/{name}.md", CustomFormat::Files);
+        assert_eq!(files.config_dir(), PathBuf::from(".acme/rules"));
+        assert_eq!(files.skills_dir(), PathBuf::from(".acme/rules/skills"));
+        assert_eq!(adapter("AGENTS.md", CustomFormat::Markdown).config_dir(), PathBuf::from("."));
+    }
+
+    #[test]
+    fn test_custom_files_ownership() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let rules = temp_dir.path().join("rules");
+        let adapter = CustomAdapter {
+            name: "Acme".to_string(),
+            target: rules.join("{name}.md").to_string_lossy().into_owned(),
+            template: None,
+            format: CustomFormat::Files,
+            skills_dir: None,
+        };
+
+        adapter.inject_skill("python", Path::new("store/python/SKILL.md"), None).unwrap();
+        fs::write(rules.join("style.md"), "# My own style rules\n").unwrap();
+        fs::write(rules.join("rust.md"), "# Rust\n\n<!-- skillctl:skill python -->\n").unwrap();
+        assert_eq!(adapter.managed_references().unwrap(), vec!["python"]);
+
+        // Files the user wrote are never removed, even under a skill's name
+        adapter.remove_skill("style").unwrap();
+        adapter.remove_skill("rust").unwrap();
+        adapter.remove_skill("python").unwrap();
+        assert!(rules.join("style.md").exists());
+        assert!(rules.join("rust.md").exists());
+        assert!(!rules.join("python.md").exists());
+    }
+
+    #[test]
+    fn test_custom_adapter_template() {
+        let adapter = CustomAdapter {
+            name: "Acme".to_string(),
+            target: "AGENTS.md".to_string(),
+            template: Some("- {name}: {description} ({path})".to_string()),
+            format: CustomFormat::Markdown,
+            skills_dir: None,
+        };
+
+        let rendered = adapter.render("python", Path::new(".skillctl/store/python/SKILL.md"));
+        assert_eq!(rendered, "- python: Skill python (.skillctl/store/python/SKILL.md)");
+
+        // Tagged references survive a parse/render round trip whatever the template looks like
+        let mut managed = ManagedFile::parse("# Agents\n").unwrap();
+        managed.set_skill("python", &ManagedFile::tagged_reference("python", &rendered));
+        managed.set_skill("rust", &ManagedFile::tagged_reference("rust", "- Skill (rust) -> x\n  indented line"));
+        let reparsed = ManagedFile::parse(&managed.render()).unwrap();
+        assert_eq!(reparsed.skill_names(), vec!["python", "rust"]);
+        assert_eq!(reparsed.render(), managed.render());
+    }
+}
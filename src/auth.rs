@@ -0,0 +1,250 @@
+//! Tokens for private repositories, from `GITHUB_TOKEN`/`GITLAB_TOKEN`, the
+//! `skillctl auth` credential file or `~/.netrc`. A token is only ever sent to
+//! the host it was given for, and never written to skills.json or skills.lock.
+
+use anyhow::{Result, Context};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use crate::scope::Scope;
+use crate::security::validate_trusted_host;
+
+/// Credential file written by `skillctl auth login`, next to the global skills.json
+const CREDENTIALS_FILE: &str = "credentials.json";
+
+/// Environment variables holding a token, and the host each one is for
+const TOKEN_VARS: &[(&str, &str)] = &[
+    ("GITHUB_TOKEN", "github.com"),
+    ("GITLAB_TOKEN", "gitlab.com"),
+];
+
+/// Where a token was found
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CredentialSource {
+    Env(&'static str),
+    File,
+    Netrc,
+}
+
+impl fmt::Display for CredentialSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CredentialSource::Env(var) => write!(f, "{}", var),
+            CredentialSource::File => write!(f, "skillctl auth"),
+            CredentialSource::Netrc => write!(f, ".netrc"),
+        }
+    }
+}
+
+/// Tokens by host. Deliberately not `Debug`, so tokens cannot end up in logs.
+#[derive(Default)]
+pub struct Credentials {
+    tokens: BTreeMap<String, (String, CredentialSource)>,
+}
+
+impl Credentials {
+    /// Collect tokens from every source. The environment wins over the credential
+    /// file, which wins over `.netrc`.
+    pub fn load() -> Result<Self> {
+        let mut credentials = Self::default();
+
+        if let Some(path) = netrc_path() {
+            if let Ok(content) = fs::read_to_string(&path) {
+                credentials.add_netrc(&content);
+            }
+        }
+        for (host, token) in load_token_file(&credentials_path()?)? {
+            credentials.insert(&host, &token, CredentialSource::File);
+        }
+        for (var, host) in TOKEN_VARS {
+            if let Ok(token) = std::env::var(var) {
+                credentials.insert(host, &token, CredentialSource::Env(var));
+            }
+        }
+
+        Ok(credentials)
+    }
+
+    /// Use `token` for `host`, replacing any token found earlier. Blank tokens are ignored.
+    pub fn insert(&mut self, host: &str, token: &str, source: CredentialSource) {
+        let token = token.trim();
+        if !token.is_empty() {
+            self.tokens.insert(credential_host(host), (token.to_string(), source));
+        }
+    }
+
+    /// The token to send to `host`, if one was given for it
+    pub fn token_for(&self, host: &str) -> Option<&str> {
+        self.tokens.get(&credential_host(host)).map(|(token, _)| token.as_str())
+    }
+
+    /// Hosts with a token, and where each token came from
+    pub fn sources(&self) -> impl Iterator<Item = (&str, CredentialSource)> {
+        self.tokens.iter().map(|(host, (_, source))| (host.as_str(), *source))
+    }
+
+    /// Replace every known token in `text`, for error messages
+    pub fn redact(&self, text: &str) -> String {
+        self.tokens.values()
+            .fold(text.to_string(), |text, (token, _)| text.replace(token.as_str(), "****"))
+    }
+
+    /// Read `machine <host> ... password <token>` entries. `default` entries are
+    /// ignored: a token is never sent to a host it was not given for.
+    fn add_netrc(&mut self, content: &str) {
+        let mut words = content.split_whitespace();
+        let mut machine: Option<&str> = None;
+        while let Some(word) = words.next() {
+            match word {
+                "machine" => machine = words.next(),
+                "default" => machine = None,
+                "password" => {
+                    if let (Some(host), Some(token)) = (machine, words.next()) {
+                        self.insert(host, token, CredentialSource::Netrc);
+                    }
+                },
+                // Macro bodies run until a blank line, which whitespace splitting loses;
+                // they never hold credentials, so stop reading instead
+                "macdef" => break,
+                _ => {},
+            }
+        }
+    }
+}
+
+/// GitHub serves API and raw content from other hosts than github.com, under the same token
+fn credential_host(host: &str) -> String {
+    let host = host.to_ascii_lowercase();
+    match host.as_str() {
+        "www.github.com" | "api.github.com" | "raw.githubusercontent.com" => "github.com".to_string(),
+        "www.gitlab.com" => "gitlab.com".to_string(),
+        _ => host,
+    }
+}
+
+fn netrc_path() -> Option<PathBuf> {
+    std::env::var_os("NETRC")
+        .map(PathBuf::from)
+        .or_else(|| dirs::home_dir().map(|home| home.join(".netrc")))
+}
+
+/// The `skillctl auth` credential file in the user's config directory
+pub fn credentials_path() -> Result<PathBuf> {
+    Ok(Scope::global()?.config_path().with_file_name(CREDENTIALS_FILE))
+}
+
+/// Tokens stored by `skillctl auth login`, by host
+pub fn load_token_file(path: &Path) -> Result<BTreeMap<String, String>> {
+    if !path.exists() {
+        return Ok(BTreeMap::new());
+    }
+    let content = fs::read_to_string(path)
+        .context("Failed to read the credential file")?;
+    // The parse error could quote a token, so it is not passed on
+    serde_json::from_str(&content)
+        .map_err(|_| anyhow::anyhow!("Failed to parse the credential file {}", path.display()))
+}
+
+/// Write the credential file, readable only by the user
+pub fn save_token_file(path: &Path, tokens: &BTreeMap<String, String>) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .context("Failed to create config directory")?;
+    }
+
+    let content = serde_json::to_string_pretty(tokens)?;
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+        options.mode(0o600);
+        // Files created before (or by hand) keep their mode otherwise
+        if path.exists() {
+            fs::set_permissions(path, fs::Permissions::from_mode(0o600))
+                .context("Failed to restrict the credential file's permissions")?;
+        }
+    }
+
+    let mut file = options.open(path)
+        .context("Failed to write the credential file")?;
+    file.write_all(content.as_bytes())
+        .context("Failed to write the credential file")?;
+    Ok(())
+}
+
+/// Normalize and check a host given to `skillctl auth`
+pub fn validate_auth_host(host: &str) -> Result<String> {
+    let host = credential_host(host.trim());
+    validate_trusted_host(&host)
+        .with_context(|| format!("Cannot store a token for '{}'", host))?;
+    Ok(host)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_tokens_match_their_host_only() {
+        let mut credentials = Credentials::default();
+        credentials.insert("github.com", "gh-secret", CredentialSource::Env("GITHUB_TOKEN"));
+        credentials.insert("git.example.com", "ex-secret", CredentialSource::File);
+
+        assert_eq!(credentials.token_for("github.com"), Some("gh-secret"));
+        assert_eq!(credentials.token_for("raw.githubusercontent.com"), Some("gh-secret"));
+        assert_eq!(credentials.token_for("API.GitHub.com"), Some("gh-secret"));
+        assert_eq!(credentials.token_for("git.example.com"), Some("ex-secret"));
+        assert_eq!(credentials.token_for("gitlab.com"), None);
+        assert_eq!(credentials.token_for("github.com.evil.com"), None);
+        assert_eq!(credentials.token_for("example.com"), None);
+
+        assert_eq!(
+            credentials.redact("GET https://x/?t=gh-secret failed (ex-secret)"),
+            "GET https://x/?t=**** failed (****)"
+        );
+    }
+
+    #[test]
+    fn test_netrc() {
+        let mut credentials = Credentials::default();
+        credentials.add_netrc("machine gitlab.com login me password glpat-1\n\
+            machine git.example.com\n  login me\n  password tok-2\n\
+            default login anonymous password everywhere\n");
+
+        assert_eq!(credentials.token_for("gitlab.com"), Some("glpat-1"));
+        assert_eq!(credentials.token_for("git.example.com"), Some("tok-2"));
+        assert_eq!(credentials.token_for("github.com"), None);
+        assert_eq!(credentials.sources().count(), 2);
+        assert!(credentials.sources().all(|(_, source)| source == CredentialSource::Netrc));
+    }
+
+    #[test]
+    fn test_token_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("config/credentials.json");
+        assert!(load_token_file(&path).unwrap().is_empty());
+
+        let tokens = BTreeMap::from([("github.com".to_string(), "gh-secret".to_string())]);
+        save_token_file(&path, &tokens).unwrap();
+        assert_eq!(load_token_file(&path).unwrap(), tokens);
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            assert_eq!(fs::metadata(&path).unwrap().permissions().mode() & 0o777, 0o600);
+        }
+
+        // Parse errors must not echo the file's content
+        fs::write(&path, "{\"github.com\": gh-secret}").unwrap();
+        let err = load_token_file(&path).unwrap_err();
+        assert!(!format!("{:#}", err).contains("gh-secret"));
+
+        assert_eq!(validate_auth_host("API.github.com").unwrap(), "github.com");
+        assert!(validate_auth_host("https://github.com").is_err());
+        assert!(validate_auth_host("192.168.1.10").is_err());
+    }
+}
@@ -1,20 +1,49 @@
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
 
 #[derive(Parser)]
 #[command(name = "skillctl", version = "0.0.9", about = "Secure AI Skill Manager")]
 pub struct Cli {
+    /// Answer yes to every confirmation prompt
+    #[arg(long, short = 'y', global = true)]
+    pub yes: bool,
+
+    /// Never prompt; fail instead when input would be required
+    #[arg(long, global = true)]
+    pub non_interactive: bool,
+
+    /// Manage user-level skills in ~/.config/skillctl instead of the project
+    #[arg(long, short = 'g', global = true)]
+    pub global: bool,
+
+    /// Output format for list, search, auth status, store gc and memory list/search
+    #[arg(long, value_enum, default_value_t = OutputFormat::Text, global = true)]
+    pub format: OutputFormat,
+
     #[command(subcommand)]
     pub command: Commands,
 }
 
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Styled output for humans
+    Text,
+    /// Stable JSON on stdout for scripts and agents
+    Json,
+}
+
 #[derive(Subcommand)]
 pub enum Commands {
     /// Initialize skillctl in the current directory
-    Init,
+    Init {
+        /// Editors to configure, comma-separated (e.g., cursor,claude-code)
+        #[arg(long, value_delimiter = ',')]
+        editors: Vec<String>,
+    },
     
-    /// Add a skill from a repository
+    /// Add a skill from a repository or a local directory
     Add { 
-        /// Repository URL (e.g., https://github.com/user/repo)
+        /// Repository URL (e.g., https://github.com/user/repo), qualified id (user/repo:skill)
+        /// or local skill directory (./path/to/skill, file:///path/to/skill)
         url: String,
         
         /// Skill name to install
@@ -28,19 +57,51 @@ pub enum Commands {
         /// List available skills without installing
         #[arg(long, short = 'l')] 
         list: bool,
+
+        /// Branch, tag or commit to install from (default: the repository's default branch).
+        /// Also accepted as <url>@<tag-or-sha> or <url>#<branch>
+        #[arg(long = "ref", value_name = "REF")]
+        reference: Option<String>,
+
+        /// Install under another name, e.g. to keep same-named skills from different repositories
+        #[arg(long = "as", value_name = "ALIAS")]
+        alias: Option<String>,
+
+        /// Link a local skill directory instead of copying it, so edits apply immediately
+        #[arg(long)]
+        dev: bool,
     },
     
     /// Remove installed skills
     Remove {
-        /// Names of skills to remove
+        /// Names or qualified ids (user/repo:skill) of skills to remove
         #[arg(required = true)]
         skills: Vec<String>,
     },
     
+    /// Update installed skills to their latest upstream version
+    Update {
+        /// Names of skills to update (all if omitted, use --yes to accept all)
+        skills: Vec<String>,
+    },
+    
+    /// Report installed skills that changed upstream (exits non-zero if any)
+    Outdated {
+        /// Names of skills to check (all if omitted)
+        skills: Vec<String>,
+    },
+    
     /// Restore skills from skills.json
     Install,
     
-    /// Search the community registry
+    /// Check store and editor files against skills.json
+    Verify {
+        /// Repair problems (restore content, relink, remove orphans)
+        #[arg(long)]
+        fix: bool,
+    },
+    
+    /// Search the community registry (with --format json, print the whole registry)
     Search,
     
     /// List installed skills
@@ -49,6 +110,38 @@ pub enum Commands {
     /// Manage Active Memory
     #[command(subcommand)]
     Memory(MemoryCommands),
+
+    /// Manage the shared content-addressed skill store
+    #[command(subcommand)]
+    Store(StoreCommands),
+
+    /// Manage tokens for private repositories
+    #[command(subcommand)]
+    Auth(AuthCommands),
+}
+
+#[derive(Subcommand)]
+pub enum StoreCommands {
+    /// Remove stored skill versions no project or global store links to
+    Gc,
+}
+
+#[derive(Subcommand)]
+pub enum AuthCommands {
+    /// Store a token for a host (prompted, or read from stdin)
+    Login {
+        /// Host the token is for (e.g., github.com, git.example.com)
+        host: String,
+    },
+
+    /// Delete the stored token for a host
+    Logout {
+        /// Host to forget
+        host: String,
+    },
+
+    /// Show which hosts have a token and where it comes from
+    Status,
 }
 
 #[derive(Subcommand)]
@@ -1,9 +1,15 @@
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::{Path, PathBuf};
 use std::fs;
 use anyhow::{Result, Context};
 use strum_macros::{EnumIter, Display};
+use crate::adapters::{self, CustomAdapter, EditorAdapter};
+use crate::manifest::SkillManifest;
+use crate::scope::Scope;
+use crate::providers::{repo_id, TrustedHosts};
+use crate::security::validate_trusted_host;
+use crate::store::BlobStore;
 
 #[derive(Debug, Clone, Serialize, Deserialize, EnumIter, Display, PartialEq, Eq, Hash)]
 pub enum EditorType {
@@ -21,53 +27,74 @@ pub enum EditorType {
     VSCode,
 }
 
+/// How a skill is made available to an editor (skills.json `delivery`)
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Display, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum Delivery {
+    /// Point the editor's config at the skill in the store
+    Reference,
+    /// Copy the skill directory into the editor's `skills_dir()`
+    Copy,
+    /// Symlink the skill directory into the editor's `skills_dir()` (copies where links are unavailable)
+    Symlink,
+}
+
+/// Marker file identifying a skill directory copied into an editor by skillctl
+const MANAGED_COPY_MARKER: &str = ".skillctl-managed";
+
 impl EditorType {
-    pub fn config_file(&self) -> PathBuf {
+    /// The adapter implementing this editor's conventions
+    pub fn adapter(&self) -> Box<dyn EditorAdapter> {
         match self {
-            EditorType::Cursor => PathBuf::from(".cursorrules"),
-            EditorType::Windsurf => PathBuf::from(".windsurfrules"),
-            EditorType::Antigravity => PathBuf::from(".agent/rules.md"),
-            EditorType::ClaudeCode => PathBuf::from(".claude/config"),
-            EditorType::Cline => PathBuf::from(".cline/config"),
-            EditorType::Roo => PathBuf::from(".roo/config"),
-            EditorType::OpenHands => PathBuf::from(".openhands/config"),
-            EditorType::Trae => PathBuf::from(".trae/config"),
-            EditorType::Copilot => PathBuf::from(".github/copilot-instructions.md"),
-            EditorType::Continue => PathBuf::from(".continue/config.json"),
-            EditorType::VSCode => PathBuf::from(".vscode/settings.json"),
+            EditorType::Cursor => Box::new(adapters::CursorAdapter::default()),
+            EditorType::Windsurf => Box::new(adapters::WindsurfAdapter::default()),
+            EditorType::Antigravity => Box::new(adapters::AntigravityAdapter),
+            EditorType::ClaudeCode => Box::new(adapters::ClaudeCodeAdapter::default()),
+            EditorType::Cline => Box::new(adapters::ClineAdapter),
+            EditorType::Roo => Box::new(adapters::RooAdapter),
+            EditorType::OpenHands => Box::new(adapters::OpenHandsAdapter),
+            EditorType::Trae => Box::new(adapters::TraeAdapter),
+            EditorType::Copilot => Box::new(adapters::CopilotAdapter),
+            EditorType::Continue => Box::new(adapters::ContinueAdapter::default()),
+            EditorType::VSCode => Box::new(adapters::VSCodeAdapter),
         }
     }
 
-    pub fn skills_dir(&self) -> PathBuf {
+    /// The adapter for the editor's user-level location under `home`, if it has one
+    pub fn user_adapter(&self, home: &Path) -> Option<Box<dyn EditorAdapter>> {
+        let home = Some(home.to_path_buf());
         match self {
-            EditorType::Cursor => PathBuf::from(".cursor/skills"),
-            EditorType::Windsurf => PathBuf::from(".windsurf/skills"),
-            EditorType::Antigravity => PathBuf::from(".agent/skills"),
-            EditorType::ClaudeCode => PathBuf::from(".claude/skills"),
-            EditorType::Cline => PathBuf::from(".cline/skills"),
-            EditorType::Roo => PathBuf::from(".roo/skills"),
-            EditorType::OpenHands => PathBuf::from(".openhands/skills"),
-            EditorType::Trae => PathBuf::from(".trae/skills"),
-            EditorType::Copilot => PathBuf::from(".github/skills"),
-            EditorType::Continue => PathBuf::from(".continue/skills"),
-            EditorType::VSCode => PathBuf::from(".vscode/skills"),
+            EditorType::Cursor => Some(Box::new(adapters::CursorAdapter { home })),
+            EditorType::Windsurf => Some(Box::new(adapters::WindsurfAdapter { home })),
+            EditorType::ClaudeCode => Some(Box::new(adapters::ClaudeCodeAdapter { home })),
+            EditorType::Continue => Some(Box::new(adapters::ContinueAdapter { home })),
+            _ => None,
         }
     }
+}
 
-    pub fn config_dir(&self) -> PathBuf {
-        match self {
-            EditorType::Cursor => PathBuf::from(".cursor"),
-            EditorType::Windsurf => PathBuf::from(".windsurf"),
-            EditorType::Antigravity => PathBuf::from(".agent"),
-            EditorType::ClaudeCode => PathBuf::from(".claude"),
-            EditorType::Cline => PathBuf::from(".cline"),
-            EditorType::Roo => PathBuf::from(".roo"),
-            EditorType::OpenHands => PathBuf::from(".openhands"),
-            EditorType::Trae => PathBuf::from(".trae"),
-            EditorType::Copilot => PathBuf::from(".github"),
-            EditorType::Continue => PathBuf::from(".continue"),
-            EditorType::VSCode => PathBuf::from(".vscode"),
+impl std::str::FromStr for EditorType {
+    type Err = anyhow::Error;
+
+    /// Parse an editor name case-insensitively, ignoring dashes and underscores
+    /// (e.g. `cursor`, `claude-code`, `github-copilot`)
+    fn from_str(name: &str) -> Result<Self> {
+        use strum::IntoEnumIterator;
+
+        let normalize = |s: &str| s.chars()
+            .filter(|c| c.is_alphanumeric())
+            .collect::<String>()
+            .to_lowercase();
+        let wanted = normalize(name);
+
+        if wanted == "githubcopilot" {
+            return Ok(EditorType::Copilot);
         }
+
+        EditorType::iter()
+            .find(|editor| normalize(&editor.to_string()) == wanted)
+            .with_context(|| format!("Unknown editor '{}'", name))
     }
 }
 
@@ -76,6 +103,196 @@ pub struct SkillConfig {
     pub active_editors: Vec<EditorType>,
     pub store_path: String,
     pub skills: HashMap<String, SkillEntry>,
+    /// Per-project overrides of the rule metadata declared by each skill
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub rule_overrides: HashMap<String, RuleOverrides>,
+    /// Per-editor delivery strategy, keyed by editor name (defaults to `EditorAdapter::default_delivery`)
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub delivery: HashMap<String, Delivery>,
+    /// Editors skillctl does not know about, always active when declared
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub adapters: Vec<CustomAdapter>,
+    /// Self-hosted forges skills may be fetched from, each opted in by host name
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub trusted_hosts: TrustedHosts,
+    /// Whether a project also delivers the global skills it does not override
+    #[serde(default = "inherit_global_default", skip_serializing_if = "is_inherit_global_default")]
+    pub inherit_global: bool,
+    /// Scope the configuration was loaded from (not serialized)
+    #[serde(skip)]
+    pub scope: Scope,
+}
+
+fn inherit_global_default() -> bool {
+    true
+}
+
+fn is_inherit_global_default(inherit_global: &bool) -> bool {
+    *inherit_global
+}
+
+impl SkillConfig {
+    pub fn delivery_for(&self, adapter: &dyn EditorAdapter) -> Delivery {
+        self.delivery.get(adapter.name()).copied()
+            .unwrap_or_else(|| adapter.default_delivery())
+    }
+
+    /// Adapters of the active built-in editors followed by the user-defined ones.
+    /// In the global scope, editors without a user-level location are skipped.
+    pub fn active_adapters(&self) -> Result<Vec<Box<dyn EditorAdapter>>> {
+        let mut active: Vec<Box<dyn EditorAdapter>> = self.active_editors.iter()
+            .filter_map(|editor| self.scope.adapter(editor))
+            .collect();
+
+        if self.scope.is_global() && !self.adapters.is_empty() {
+            anyhow::bail!("User-defined adapters are only supported in a project skills.json");
+        }
+        for custom in &self.adapters {
+            custom.validate()?;
+            if active.iter().any(|a| a.name() == custom.name) {
+                anyhow::bail!("Adapter '{}' is declared more than once", custom.name);
+            }
+            active.push(Box::new(custom.clone()));
+        }
+        Ok(active)
+    }
+
+    /// Hosts skills may be fetched from besides GitHub and GitLab: the global
+    /// config's `trusted_hosts` plus the project's, which take precedence
+    pub fn effective_trusted_hosts(&self) -> Result<TrustedHosts> {
+        let mut hosts = TrustedHosts::new();
+        if !self.scope.is_global() {
+            if let Ok(global) = Scope::global() {
+                if global.config_path().exists() {
+                    hosts.extend(load_config(&global)?.trusted_hosts);
+                }
+            }
+        }
+        hosts.extend(self.trusted_hosts.iter().map(|(host, forge)| (host.to_ascii_lowercase(), *forge)));
+
+        for host in hosts.keys() {
+            validate_trusted_host(host)?;
+        }
+        Ok(hosts)
+    }
+
+    /// The installed name of a skill given either that name or its qualified id
+    pub fn resolve_skill_name(&self, name_or_id: &str) -> Result<Option<String>> {
+        if self.skills.contains_key(name_or_id) {
+            return Ok(Some(name_or_id.to_string()));
+        }
+
+        let matches: Vec<&String> = self.skills.iter()
+            .filter(|(name, entry)| entry.qualified_id(name) == name_or_id)
+            .map(|(name, _)| name)
+            .collect();
+        match matches.as_slice() {
+            [] => Ok(None),
+            [name] => Ok(Some((*name).clone())),
+            _ => anyhow::bail!("{} is installed more than once; use one of its aliases", name_or_id),
+        }
+    }
+
+    /// Fail if `name` is already taken by a different skill than `skill` from `repo_url`
+    pub fn check_name_conflict(&self, name: &str, repo_url: &str, skill: &str) -> Result<()> {
+        let Some(existing) = self.skills.get(name) else {
+            return Ok(());
+        };
+
+        let existing_id = existing.qualified_id(name);
+        let requested_id = format!("{}:{}", repo_id(repo_url), skill);
+        if existing_id != requested_id {
+            anyhow::bail!(
+                "'{}' is already installed from {}. Use --as <alias> to install {} alongside it.",
+                name, existing_id, requested_id
+            );
+        }
+        Ok(())
+    }
+
+    /// The global configuration a project inherits skills from, if any
+    pub fn load_inherited(&self) -> Result<Option<SkillConfig>> {
+        if self.scope.is_global() || !self.inherit_global {
+            return Ok(None);
+        }
+        let Ok(global) = Scope::global() else {
+            return Ok(None);
+        };
+        if !global.config_path().exists() {
+            return Ok(None);
+        }
+        load_config(&global).map(Some)
+    }
+
+    /// Global skills delivered to this project: every one it does not override
+    /// with a project skill of the same name
+    pub fn inherited_skills<'a>(&self, global: &'a SkillConfig) -> Vec<(&'a String, &'a SkillEntry)> {
+        let mut inherited: Vec<_> = global.skills.iter()
+            .filter(|(name, _)| !self.skills.contains_key(*name))
+            .collect();
+        inherited.sort_by(|a, b| a.0.cmp(b.0));
+        inherited
+    }
+
+    /// Project adapters that need inherited skills written into the project:
+    /// those whose editor the global config does not already serve at user level
+    pub fn inheriting_adapters<'a>(adapters: &'a [Box<dyn EditorAdapter>], global: &SkillConfig) -> Result<Vec<&'a dyn EditorAdapter>> {
+        let user_level: Vec<String> = global.active_adapters()?.iter()
+            .map(|a| a.name().to_string())
+            .collect();
+        Ok(adapters.iter()
+            .map(|a| a.as_ref())
+            .filter(|a| !user_level.iter().any(|name| name == a.name()))
+            .collect())
+    }
+}
+
+/// Project-level replacements for a skill's frontmatter (skills.json `rule_overrides`)
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct RuleOverrides {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub globs: Option<Vec<String>>,
+    #[serde(default, rename = "alwaysApply", skip_serializing_if = "Option::is_none")]
+    pub always_apply: Option<bool>,
+}
+
+/// Metadata written into a generated editor rule (e.g. Cursor `.mdc` frontmatter)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub(crate) struct RuleMetadata {
+    pub description: String,
+    pub globs: String,
+    #[serde(rename = "alwaysApply")]
+    pub always_apply: bool,
+}
+
+impl RuleMetadata {
+    /// Combine the skill's own frontmatter with project overrides
+    pub fn resolve(skill_name: &str, manifest: Option<&SkillManifest>, overrides: Option<&RuleOverrides>) -> Self {
+        let description = overrides.and_then(|o| o.description.clone())
+            .or_else(|| manifest.map(|m| m.description.clone()))
+            .map(|d| d.replace('\n', " ").trim().to_string())
+            .filter(|d| !d.is_empty())
+            .unwrap_or_else(|| format!("Skill {}", skill_name));
+
+        let globs = overrides.and_then(|o| o.globs.clone())
+            .or_else(|| manifest.map(|m| m.globs.clone()).filter(|g| !g.is_empty()))
+            .map(|g| g.join(","))
+            .unwrap_or_else(|| "*".to_string());
+
+        let always_apply = overrides.and_then(|o| o.always_apply)
+            .or_else(|| manifest.and_then(|m| m.always_apply))
+            .unwrap_or(false);
+
+        Self { description, globs, always_apply }
+    }
+
+    /// YAML frontmatter body, quoted wherever the values need it
+    pub fn to_frontmatter(&self) -> Result<String> {
+        serde_yaml::to_string(self)
+            .context("Failed to serialize rule metadata")
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -84,18 +301,46 @@ pub struct SkillEntry {
     pub local_path: String,
     pub hash: String,
     pub last_updated: String,
+    /// Path of SKILL.md inside the repository
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    /// Commit SHA (or branch) the skill was installed from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resolved_ref: Option<String>,
+    /// Branch, tag or commit requested at install (`--ref`, `<url>@<tag>`, `<url>#<branch>`).
+    /// `update` follows it instead of the default branch.
+    #[serde(default, rename = "ref", skip_serializing_if = "Option::is_none")]
+    pub reference: Option<String>,
+    /// Per-file SHA256 hashes for skills with supporting files (`hash` is their aggregate)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub files: BTreeMap<String, String>,
+    /// Upstream skill name, when installed under an alias (`add --as`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub skill: Option<String>,
+    /// Linked to its local source directory instead of copied (`add --dev`)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub dev: bool,
 }
 
-pub fn default_store_path() -> String {
-    ".skillctl/store".to_string()
+impl SkillEntry {
+    /// The skill's name in its repository; `name` is the key it is installed under
+    pub fn upstream_name<'a>(&'a self, name: &'a str) -> &'a str {
+        self.skill.as_deref().unwrap_or(name)
+    }
+
+    /// Qualified identifier of the skill: `owner/repo:skill`
+    pub fn qualified_id(&self, name: &str) -> String {
+        format!("{}:{}", repo_id(&self.url), self.upstream_name(name))
+    }
 }
 
-pub fn load_config() -> Result<SkillConfig> {
-    let content = fs::read_to_string("skills.json")
+pub fn load_config(scope: &Scope) -> Result<SkillConfig> {
+    let content = fs::read_to_string(scope.config_path())
         .context("Configuration file not found. Please run 'skillctl init' first.")?;
     
-    let config: SkillConfig = serde_json::from_str(&content)
+    let mut config: SkillConfig = serde_json::from_str(&content)
         .context("Failed to parse skills.json. The file may be corrupted.")?;
+    config.scope = scope.clone();
     
     Ok(config)
 }
@@ -103,22 +348,27 @@ pub fn load_config() -> Result<SkillConfig> {
 pub fn save_config(config: &SkillConfig) -> Result<()> {
     let json = serde_json::to_string_pretty(config)
         .context("Failed to serialize configuration")?;
-    
-    fs::write("skills.json", json)
+
+    let path = config.scope.config_path();
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)
+            .context("Failed to create configuration directory")?;
+    }
+    fs::write(path, json)
         .context("Failed to write skills.json")?;
     
     Ok(())
 }
 
 /// Auto-detect installed editors by checking for their config directories
-pub fn detect_installed_editors() -> Vec<EditorType> {
+/// (user-level locations in the global scope)
+pub fn detect_installed_editors(scope: &Scope) -> Vec<EditorType> {
     use strum::IntoEnumIterator;
     
     let mut detected = Vec::new();
     
     for editor in EditorType::iter() {
-        let config_dir = editor.config_dir();
-        if config_dir.exists() {
+        if scope.adapter(&editor).is_some_and(|adapter| adapter.detect()) {
             detected.push(editor);
         }
     }
@@ -126,179 +376,378 @@ pub fn detect_installed_editors() -> Vec<EditorType> {
     detected
 }
 
-/// Inject a skill reference into an editor's configuration
-pub fn inject_reference(editor: &EditorType, skill_name: &str, skill_path: &Path) -> Result<()> {
-    let relative_path = skill_path.to_string_lossy();
-    
-    // CASO ESPECIAL: Cursor usa .cursor/rules/*.mdc
-    if let EditorType::Cursor = editor {
-        let rules_dir = Path::new(".cursor/rules");
-        fs::create_dir_all(rules_dir)
-            .context("Failed to create .cursor/rules directory")?;
-
-        let rule_file = rules_dir.join(format!("{}.mdc", skill_name));
-        let content = format!(
-            "---\ndescription: Skill {}\nglobs: *\n---\n# {}\n\nRead logic from: {}\n",
-            skill_name,
-            skill_name,
-            relative_path
-        );
-        fs::write(&rule_file, content)
-            .context("Failed to write Cursor rule file")?;
+/// Make a skill available to an editor using the given delivery strategy.
+/// Whatever another strategy left behind for the same skill is removed.
+pub fn inject_reference(adapter: &dyn EditorAdapter, skill_name: &str, skill_path: &Path, overrides: Option<&RuleOverrides>, delivery: Delivery) -> Result<()> {
+    let target_dir = adapter.skills_dir().join(skill_name);
+
+    if delivery == Delivery::Reference {
+        remove_materialized_skill(&target_dir)?;
+        return adapter.inject_skill(skill_name, skill_path, overrides);
+    }
+
+    let source_dir = skill_path.parent()
+        .context("Skill path has no parent directory")?;
+    materialize_skill_dir(source_dir, &target_dir, delivery)?;
+    adapter.remove_skill(skill_name)
+}
+
+/// Whether a copy delivered to an editor no longer matches the store
+/// (or a link no longer points at it)
+pub fn is_delivery_stale(adapter: &dyn EditorAdapter, skill_name: &str, skill_path: &Path, delivery: Delivery) -> Result<bool> {
+    let target_dir = adapter.skills_dir().join(skill_name);
+    if delivery == Delivery::Reference || !is_materialized_skill(&target_dir) {
+        return Ok(false);
+    }
+
+    let source_dir = skill_path.parent()
+        .context("Skill path has no parent directory")?;
+    Ok(read_tree(source_dir)? != read_tree(&target_dir)?)
+}
+
+/// Remove a skill from an editor, whichever delivery strategy put it there
+pub fn remove_reference(adapter: &dyn EditorAdapter, skill_name: &str) -> Result<()> {
+    remove_materialized_skill(&adapter.skills_dir().join(skill_name))?;
+    adapter.remove_skill(skill_name)
+}
+
+/// List the skills skillctl delivered to an editor (pointers and skill directories)
+pub fn list_references(adapter: &dyn EditorAdapter) -> Result<Vec<String>> {
+    let mut names = adapter.managed_references()?;
+    names.extend(list_materialized_skills(&adapter.skills_dir())?);
+    names.sort();
+    names.dedup();
+    Ok(names)
+}
+
+/// Make a store skill directory visible at `target_dir`: a relative symlink
+/// when asked for and the platform allows it, otherwise a marked copy
+fn materialize_skill_dir(source_dir: &Path, target_dir: &Path, delivery: Delivery) -> Result<()> {
+    remove_materialized_skill(target_dir)?;
+    if target_dir.exists() {
+        anyhow::bail!("{} exists and was not created by skillctl", target_dir.display());
+    }
+
+    let parent = target_dir.parent()
+        .context("Skill target has no parent directory")?;
+    fs::create_dir_all(parent)
+        .context("Failed to create editor skills directory")?;
+
+    if delivery == Delivery::Symlink && symlink_dir(&link_target(source_dir, parent), target_dir).is_ok() {
         return Ok(());
     }
+    copy_skill_dir(source_dir, target_dir)
+}
+
+/// Path to `source` as seen from inside `from_dir` (relative when both are relative)
+fn link_target(source: &Path, from_dir: &Path) -> PathBuf {
+    if source.is_absolute() || from_dir.is_absolute() {
+        return source.canonicalize().unwrap_or_else(|_| source.to_path_buf());
+    }
 
-    let config_file = editor.config_file();
-    let current_content = if config_file.exists() { 
-        fs::read_to_string(&config_file)
-            .context("Failed to read editor config file")? 
-    } else { 
-        String::new() 
-    };
-    
-    // Lógica específica por editor para inyección en archivo único
-    let injection = match editor {
-        EditorType::Antigravity => format!("\n### Skill: {}\nRefer to logic in: `{}`\n", skill_name, relative_path),
-        EditorType::Cline | EditorType::Roo => format!("\nRunning context for {}: See {}\n", skill_name, relative_path),
-        _ => format!("\n- Skill ({}) -> Read file: {}\n", skill_name, relative_path),
-    };
+    let mut target = PathBuf::new();
+    for _ in from_dir.components() {
+        target.push("..");
+    }
+    target.join(source)
+}
+
+#[cfg(unix)]
+pub(crate) fn symlink_dir(target: &Path, link: &Path) -> std::io::Result<()> {
+    std::os::unix::fs::symlink(target, link)
+}
+
+#[cfg(windows)]
+pub(crate) fn symlink_dir(target: &Path, link: &Path) -> std::io::Result<()> {
+    std::os::windows::fs::symlink_dir(target, link)
+}
+
+/// Copy a skill directory, marking the copy as managed by skillctl
+fn copy_skill_dir(source_dir: &Path, target_dir: &Path) -> Result<()> {
+    copy_dir_recursive(source_dir, target_dir)?;
+    fs::write(target_dir.join(MANAGED_COPY_MARKER), "Managed by skillctl. Changes are overwritten.\n")
+        .context("Failed to mark skill copy")
+}
+
+fn copy_dir_recursive(source: &Path, target: &Path) -> Result<()> {
+    fs::create_dir_all(target)
+        .context("Failed to create skill copy directory")?;
 
-    if !current_content.contains(&format!("Skill: {}", skill_name)) && !current_content.contains(&format!("Skill ({})", skill_name)) {
-        if let Some(parent) = config_file.parent() {
-            fs::create_dir_all(parent)
-                .context("Failed to create editor config directory")?;
+    for dir_entry in fs::read_dir(source).context("Failed to read skill directory")? {
+        let dir_entry = dir_entry?;
+        let destination = target.join(dir_entry.file_name());
+        if dir_entry.file_type()?.is_dir() {
+            copy_dir_recursive(&dir_entry.path(), &destination)?;
+        } else {
+            fs::copy(dir_entry.path(), &destination)
+                .context("Failed to copy skill file")?;
         }
-        let mut file = fs::OpenOptions::new()
-            .append(true)
-            .create(true)
-            .open(&config_file)
-            .context("Failed to open editor config file")?;
-        
-        use std::io::Write;
-        write!(file, "{}", injection)
-            .context("Failed to write to editor config file")?;
     }
     Ok(())
 }
 
-/// Remove a skill reference from an editor's configuration
-pub fn remove_reference(editor: &EditorType, skill_name: &str) -> Result<()> {
-    // CASO ESPECIAL: Cursor usa .cursor/rules/*.mdc
-    if let EditorType::Cursor = editor {
-        let rule_file = Path::new(".cursor/rules").join(format!("{}.mdc", skill_name));
-        if rule_file.exists() {
-            fs::remove_file(rule_file)
-                .context("Failed to remove Cursor rule file")?;
+/// Relative path and content of every file under `dir`, ignoring the copy marker
+fn read_tree(dir: &Path) -> Result<BTreeMap<PathBuf, Vec<u8>>> {
+    fn walk(root: &Path, dir: &Path, files: &mut BTreeMap<PathBuf, Vec<u8>>) -> Result<()> {
+        for dir_entry in fs::read_dir(dir).context("Failed to read skill directory")? {
+            let path = dir_entry?.path();
+            if path.is_dir() {
+                walk(root, &path, files)?;
+            } else if path.file_name().and_then(|n| n.to_str()) != Some(MANAGED_COPY_MARKER) {
+                let relative = path.strip_prefix(root).unwrap_or(&path).to_path_buf();
+                files.insert(relative, fs::read(&path).context("Failed to read skill file")?);
+            }
         }
-        return Ok(());
+        Ok(())
     }
 
-    let config_file = editor.config_file();
-    if !config_file.exists() {
-        return Ok(());
+    let mut files = BTreeMap::new();
+    walk(dir, dir, &mut files)?;
+    Ok(files)
+}
+
+/// Whether `path` is a skill directory skillctl put there: a link into a skill
+/// store, or a marked copy. A user's own symlinks elsewhere are left alone.
+fn is_materialized_skill(path: &Path) -> bool {
+    match fs::symlink_metadata(path) {
+        Ok(metadata) if metadata.file_type().is_symlink() => links_into_store(path),
+        Ok(metadata) if metadata.is_dir() => path.join(MANAGED_COPY_MARKER).exists(),
+        _ => false,
     }
+}
 
-    let content = fs::read_to_string(&config_file)
-        .context("Failed to read editor config file")?;
-    
-    // Remove lines that reference this skill
-    let lines: Vec<&str> = content.lines().collect();
-    let mut new_lines = Vec::new();
-    let mut skip_next = false;
-
-    for line in lines {
-        // Skip lines that mention the skill
-        if line.contains(&format!("Skill: {}", skill_name)) 
-            || line.contains(&format!("Skill ({})", skill_name))
-            || line.contains(&format!("context for {}", skill_name)) {
-            skip_next = true;
-            continue;
-        }
-        
-        // Skip the next line if it was a reference path
-        if skip_next && (line.contains("Read file:") || line.contains("Refer to logic") || line.contains("See ")) {
-            skip_next = false;
-            continue;
-        }
-        
-        skip_next = false;
-        new_lines.push(line);
+/// Whether the symlink at `link` points at an entry of a skill store or a shared blob.
+/// Broken links into a store still count, so `verify --fix` can clean them up.
+fn links_into_store(link: &Path) -> bool {
+    let Ok(target) = fs::read_link(link) else {
+        return false;
+    };
+    // Relative targets are relative to the link's directory; absolute ones replace it
+    let target = match link.parent() {
+        Some(parent) => parent.join(target),
+        None => target,
+    };
+    BlobStore::open_default()
+        .map(|blobs| blobs.owns(&target))
+        .unwrap_or(false)
+}
+
+/// Remove a skill directory skillctl created, leaving anything else alone
+fn remove_materialized_skill(path: &Path) -> Result<()> {
+    if !is_materialized_skill(path) {
+        return Ok(());
     }
 
-    fs::write(&config_file, new_lines.join("\n"))
-        .context("Failed to write updated editor config file")?;
-    
+    let metadata = fs::symlink_metadata(path)?;
+    if metadata.file_type().is_symlink() {
+        // Directory symlinks are removed with remove_dir on Windows
+        fs::remove_file(path).or_else(|_| fs::remove_dir(path))
+            .context("Failed to remove skill link")?;
+    } else {
+        fs::remove_dir_all(path)
+            .context("Failed to remove skill copy")?;
+    }
     Ok(())
 }
 
-/// Inject or update memory context in an editor's configuration
-pub fn inject_memory_context(editor: &EditorType, memory_content: &str) -> Result<()> {
-    // CASO ESPECIAL: Cursor usa .cursor/rules/memory.mdc
-    if let EditorType::Cursor = editor {
-        let rules_dir = Path::new(".cursor/rules");
-        fs::create_dir_all(rules_dir)
-            .context("Failed to create .cursor/rules directory")?;
-
-        let rule_file = rules_dir.join("memory.mdc");
-        let content = format!(
-            "---\ndescription: Global Active Memory\nglobs: *\n---\n{}",
-            memory_content
-        );
-        fs::write(&rule_file, content)
-            .context("Failed to write Cursor memory file")?;
-        return Ok(());
+/// Skills materialized by skillctl in an editor skills directory
+fn list_materialized_skills(skills_dir: &Path) -> Result<Vec<String>> {
+    let mut names = Vec::new();
+    if !skills_dir.exists() {
+        return Ok(names);
     }
 
-    // CASO ESPECIAL: Antigravity usa .agent/memory.md
-    if let EditorType::Antigravity = editor {
-        let agent_dir = Path::new(".agent");
-        if !agent_dir.exists() {
-             fs::create_dir_all(agent_dir)
-                .context("Failed to create .agent directory")?;
+    for dir_entry in fs::read_dir(skills_dir).context("Failed to read editor skills directory")? {
+        let path = dir_entry?.path();
+        if is_materialized_skill(&path) {
+            if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
+                names.push(name.to_string());
+            }
         }
-        let memory_file = agent_dir.join("memory.md");
-        fs::write(&memory_file, memory_content)
-            .context("Failed to write Antigravity memory file")?;
-        return Ok(());
     }
+    names.sort();
+    Ok(names)
+}
 
-    let config_file = editor.config_file();
-    
-    // If config file doesn't exist, create it with memory content if it has content
-    if !config_file.exists() {
-        if memory_content.trim().is_empty() {
-            return Ok(());
-        }
-        
-        if let Some(parent) = config_file.parent() {
-            fs::create_dir_all(parent)
-                .context("Failed to create editor config directory")?;
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_materialize_skill_dir() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("store/python");
+        fs::create_dir_all(source.join("scripts")).unwrap();
+        fs::write(source.join("SKILL.md"), "# Python").unwrap();
+        fs::write(source.join("scripts/run.sh"), "echo").unwrap();
+
+        let skills_dir = temp_dir.path().join(".claude/skills");
+        let target = skills_dir.join("python");
+        BlobStore::open_default().unwrap().register(&temp_dir.path().join("store")).unwrap();
+
+        // Symlink (or copy) exposes every file of the skill
+        materialize_skill_dir(&source, &target, Delivery::Symlink).unwrap();
+        assert_eq!(fs::read_to_string(target.join("SKILL.md")).unwrap(), "# Python");
+        assert!(target.join("scripts/run.sh").exists());
+        assert_eq!(list_materialized_skills(&skills_dir).unwrap(), vec!["python"]);
+
+        // Re-materializing replaces it; removing leaves the store intact
+        materialize_skill_dir(&source, &target, Delivery::Symlink).unwrap();
+        remove_materialized_skill(&target).unwrap();
+        assert!(!target.exists());
+        assert!(source.join("SKILL.md").exists());
+
+        // Copies are marked, compare equal to the store until it changes, and are removable too
+        materialize_skill_dir(&source, &target, Delivery::Copy).unwrap();
+        assert!(!fs::symlink_metadata(&target).unwrap().file_type().is_symlink());
+        assert_eq!(list_materialized_skills(&skills_dir).unwrap(), vec!["python"]);
+        assert_eq!(read_tree(&source).unwrap(), read_tree(&target).unwrap());
+        fs::write(source.join("SKILL.md"), "# Python v2").unwrap();
+        assert_ne!(read_tree(&source).unwrap(), read_tree(&target).unwrap());
+        remove_materialized_skill(&target).unwrap();
+        assert!(!target.exists());
+
+        // A user's own skill directory is never touched
+        let user_skill = skills_dir.join("mine");
+        fs::create_dir_all(&user_skill).unwrap();
+        fs::write(user_skill.join("SKILL.md"), "# Mine").unwrap();
+        assert!(list_materialized_skills(&skills_dir).unwrap().is_empty());
+        remove_materialized_skill(&user_skill).unwrap();
+        assert!(user_skill.join("SKILL.md").exists());
+        assert!(materialize_skill_dir(&source, &user_skill, Delivery::Copy).is_err());
+
+        // Nor is a user's symlink to a skill kept outside any store
+        let elsewhere = temp_dir.path().join("my-skills/linked");
+        fs::create_dir_all(&elsewhere).unwrap();
+        fs::write(elsewhere.join("SKILL.md"), "# Linked").unwrap();
+        let user_link = skills_dir.join("linked");
+        if symlink_dir(&elsewhere, &user_link).is_ok() {
+            assert!(list_materialized_skills(&skills_dir).unwrap().is_empty());
+            remove_materialized_skill(&user_link).unwrap();
+            assert!(fs::symlink_metadata(&user_link).is_ok());
+            assert!(materialize_skill_dir(&source, &user_link, Delivery::Symlink).is_err());
         }
-        fs::write(&config_file, memory_content)
-            .context("Failed to write editor config file")?;
-        return Ok(())
     }
 
-    let current_content = fs::read_to_string(&config_file)
-        .context("Failed to read editor config file")?;
-    
-    // Check if memory context already exists
-    let header = "# 🧠 Active Memory Context";
-    
-    let new_content = if current_content.contains(header) {
-        // Replace existing memory block
-        let parts: Vec<&str> = current_content.split(header).collect();
-        // Keep everything before the header, and append new memory content
-        // Note: This simplistic approach assumes memory block is attached at the end or we replace from header onwards
-        // To be safer, we assume memory block is always at the end for non-Cursor editors
-        format!("{}{}", parts[0].trim_end(), memory_content)
-    } else {
-        // Append new memory block
-        format!("{}\n\n{}", current_content.trim_end(), memory_content)
-    };
+    #[test]
+    fn test_delivery_config() {
+        let config: SkillConfig = serde_json::from_str(
+            r#"{"active_editors": ["Windsurf", "GitHub Copilot"], "store_path": ".skillctl/store", "skills": {},
+                "delivery": {"Windsurf": "copy", "Acme": "symlink"},
+                "adapters": [{"name": "Acme", "target": ".acme/rules.md"}]}"#
+        ).unwrap();
 
-    fs::write(&config_file, new_content)
-        .context("Failed to update editor config with memory")?;
-    
-    Ok(())
+        let adapters = config.active_adapters().unwrap();
+        let names: Vec<&str> = adapters.iter().map(|a| a.name()).collect();
+        assert_eq!(names, vec!["Windsurf", "GitHub Copilot", "Acme"]);
+
+        assert_eq!(config.delivery_for(adapters[0].as_ref()), Delivery::Copy);
+        assert_eq!(config.delivery_for(adapters[1].as_ref()), Delivery::Reference);
+        assert_eq!(config.delivery_for(adapters[2].as_ref()), Delivery::Symlink);
+        assert_eq!(config.delivery_for(EditorType::ClaudeCode.adapter().as_ref()), Delivery::Symlink);
+
+        let json = serde_json::to_string(&config).unwrap();
+        assert!(json.contains(r#""Windsurf":"copy""#));
+
+        // A user-defined adapter cannot shadow an active editor
+        let mut config = config;
+        config.adapters[0].name = "Windsurf".to_string();
+        assert!(config.active_adapters().is_err());
+    }
+
+    #[test]
+    fn test_global_inheritance() {
+        let parse = |json: &str| serde_json::from_str::<SkillConfig>(json).unwrap();
+        let entry = r#"{"url": "u", "local_path": "p", "hash": "h", "last_updated": "t"}"#;
+
+        let project = parse(&format!(
+            r#"{{"active_editors": ["ClaudeCode", "Cline"], "store_path": ".skillctl/store", "skills": {{"python": {e}}}}}"#, e = entry
+        ));
+        let mut global = parse(&format!(
+            r#"{{"active_editors": ["ClaudeCode", "Cline"], "store_path": "/s", "skills": {{"python": {e}, "rust": {e}}}}}"#, e = entry
+        ));
+        global.scope = Scope::Global { config_dir: PathBuf::from("/home/dev/.config/skillctl"), home: PathBuf::from("/home/dev") };
+
+        // The project's python overrides the global one
+        let inherited: Vec<&String> = project.inherited_skills(&global).into_iter().map(|(n, _)| n).collect();
+        assert_eq!(inherited, vec!["rust"]);
+
+        // Claude Code already sees ~/.claude/skills; Cline has no user-level location
+        let adapters = project.active_adapters().unwrap();
+        let names: Vec<&str> = SkillConfig::inheriting_adapters(&adapters, &global).unwrap()
+            .iter().map(|a| a.name()).collect();
+        assert_eq!(names, vec!["Cline"]);
+
+        assert!(project.inherit_global);
+        assert!(!serde_json::to_string(&project).unwrap().contains("inherit_global"));
+    }
+
+    #[test]
+    fn test_qualified_names_and_conflicts() {
+        let config: SkillConfig = serde_json::from_str(r#"{
+            "active_editors": [],
+            "store_path": ".skillctl/store",
+            "skills": {
+                "memory": {"url": "https://github.com/one/repo", "local_path": "p", "hash": "h", "last_updated": "t"},
+                "team-memory": {"url": "https://github.com/two/repo", "local_path": "p", "hash": "h", "last_updated": "t", "skill": "memory"}
+            }
+        }"#).unwrap();
+
+        let aliased = &config.skills["team-memory"];
+        assert_eq!(aliased.upstream_name("team-memory"), "memory");
+        assert_eq!(aliased.qualified_id("team-memory"), "two/repo:memory");
+
+        assert_eq!(config.resolve_skill_name("memory").unwrap().as_deref(), Some("memory"));
+        assert_eq!(config.resolve_skill_name("two/repo:memory").unwrap().as_deref(), Some("team-memory"));
+        assert_eq!(config.resolve_skill_name("three/repo:memory").unwrap(), None);
+
+        // Same source reinstalls in place, another source needs an alias
+        assert!(config.check_name_conflict("memory", "https://github.com/one/repo.git", "memory").is_ok());
+        assert!(config.check_name_conflict("memory", "https://github.com/two/repo", "memory").is_err());
+        assert!(config.check_name_conflict("other", "https://github.com/two/repo", "memory").is_ok());
+    }
+
+    #[test]
+    fn test_rule_metadata_resolution() {
+        // No frontmatter: previous defaults
+        let metadata = RuleMetadata::resolve("python", None, None);
+        assert_eq!(metadata.description, "Skill python");
+        assert_eq!(metadata.globs, "*");
+        assert!(!metadata.always_apply);
+
+        // Frontmatter values are used
+        let manifest = SkillManifest {
+            description: "Async Python".to_string(),
+            globs: vec!["*.py".to_string(), "*.pyi".to_string()],
+            always_apply: Some(false),
+            ..Default::default()
+        };
+        let metadata = RuleMetadata::resolve("python", Some(&manifest), None);
+        assert_eq!(metadata.description, "Async Python");
+        assert_eq!(metadata.globs, "*.py,*.pyi");
+
+        // Project overrides win
+        let overrides = RuleOverrides {
+            globs: Some(vec!["src/**/*.py".to_string()]),
+            always_apply: Some(true),
+            ..Default::default()
+        };
+        let metadata = RuleMetadata::resolve("python", Some(&manifest), Some(&overrides));
+        assert_eq!(metadata.description, "Async Python");
+        assert_eq!(metadata.globs, "src/**/*.py");
+        assert!(metadata.always_apply);
+
+        // Values that mean something in YAML come back unchanged
+        let metadata = RuleMetadata {
+            description: "Python: async # patterns\nalwaysApply: true".to_string(),
+            globs: "*.py,*.pyi".to_string(),
+            always_apply: false,
+        };
+        let frontmatter = metadata.to_frontmatter().unwrap();
+        let parsed: serde_yaml::Value = serde_yaml::from_str(&frontmatter).unwrap();
+        assert_eq!(parsed["description"].as_str(), Some(metadata.description.as_str()));
+        assert_eq!(parsed["globs"].as_str(), Some("*.py,*.pyi"));
+        assert_eq!(parsed["alwaysApply"].as_bool(), Some(false));
+    }
 }
@@ -0,0 +1,531 @@
+use anyhow::{Result, Context, bail};
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use std::ops::Range;
+
+/// Prefix of the Continue rule names owned by skillctl
+const CONTINUE_RULE_PREFIX: &str = "skillctl:";
+const CONTINUE_MEMORY_RULE: &str = "skillctl:memory";
+
+/// VS Code setting holding Copilot instruction files
+const COPILOT_INSTRUCTIONS_KEY: &str = "github.copilot.chat.codeGeneration.instructions";
+
+/// Parse a JSON document that may contain comments and trailing commas (JSONC).
+/// An empty document is treated as an empty object.
+pub fn parse_jsonc(content: &str) -> Result<Value> {
+    let stripped = strip_jsonc(content);
+    if stripped.trim().is_empty() {
+        return Ok(Value::Object(Map::new()));
+    }
+
+    let value: Value = serde_json::from_str(&stripped)
+        .context("Invalid JSON")?;
+
+    if !value.is_object() {
+        bail!("Expected a JSON object at the top level");
+    }
+    Ok(value)
+}
+
+/// Serialize a config document back to pretty JSON
+fn to_pretty_json(value: &Value) -> Result<String> {
+    let json = serde_json::to_string_pretty(value)
+        .context("Failed to serialize JSON config")?;
+    Ok(json + "\n")
+}
+
+/// Write `root` back over the JSONC document it was read from. Documents without
+/// comments are pretty-printed; otherwise only the top-level values that changed
+/// are replaced in the original text, so comments and layout elsewhere survive.
+/// Rewriting a value that itself contains comments is refused rather than losing them.
+pub fn update_jsonc(content: &str, root: &Value) -> Result<String> {
+    let layout = match scan_members(content) {
+        Some(layout) if layout.has_comments => layout,
+        _ => return to_pretty_json(root),
+    };
+    let original = parse_jsonc(content)?;
+    let (Some(old), Some(new)) = (original.as_object(), root.as_object()) else {
+        bail!("Expected a JSON object at the top level");
+    };
+    if let Some(key) = old.keys().find(|key| !new.contains_key(*key)) {
+        bail!("Cannot remove '{}' from a file with comments", key);
+    }
+
+    let indent = layout.members.first()
+        .map(|member| {
+            let line_start = content[..member.key_start].rfind('\n').map_or(0, |i| i + 1);
+            &content[line_start..member.key_start]
+        })
+        .filter(|indent| indent.chars().all(char::is_whitespace))
+        .unwrap_or("  ");
+    // Values are nested one level in, with the document's own indentation
+    let render = |value: &Value| -> Result<String> {
+        let mut json = Vec::new();
+        let formatter = serde_json::ser::PrettyFormatter::with_indent(indent.as_bytes());
+        value.serialize(&mut serde_json::Serializer::with_formatter(&mut json, formatter))
+            .context("Failed to serialize JSON config")?;
+        Ok(String::from_utf8(json)?.replace('\n', &format!("\n{}", indent)))
+    };
+
+    let mut edits = Vec::new();
+    let mut appended = String::new();
+    for (key, value) in new {
+        if old.get(key) == Some(value) {
+            continue;
+        }
+        // Duplicate keys resolve to the last one, as in parse_jsonc
+        match layout.members.iter().rev().find(|member| member.key == *key) {
+            Some(member) if member.has_comment => {
+                bail!("'{}' contains comments that rewriting it would lose; edit it by hand", key)
+            }
+            Some(member) => edits.push((member.value.clone(), render(value)?)),
+            None => {
+                let key = serde_json::to_string(key)?;
+                appended.push_str(&format!(",\n{}{}: {}", indent, key, render(value)?));
+            }
+        }
+    }
+    if !appended.is_empty() {
+        match layout.members.last() {
+            Some(last) => edits.push((last.value.end..last.value.end, appended)),
+            // Into an empty object, after its opening brace
+            None => {
+                let open = content.find('{').context("Expected a JSON object at the top level")?;
+                let member = appended.trim_start_matches(',');
+                edits.push((open + 1..open + 1, format!("{}\n", member)));
+            }
+        }
+    }
+
+    let mut out = content.to_string();
+    edits.sort_by_key(|(range, _)| std::cmp::Reverse(range.start));
+    for (range, text) in edits {
+        out.replace_range(range, &text);
+    }
+    Ok(out)
+}
+
+/// A member of the top-level object of a JSONC document, located in its text
+struct Member {
+    key: String,
+    key_start: usize,
+    /// Byte range of the value, without surrounding whitespace or comments
+    value: Range<usize>,
+    /// Whether a comment sits between the value's start and the next member
+    has_comment: bool,
+}
+
+struct Layout {
+    members: Vec<Member>,
+    has_comments: bool,
+}
+
+/// Locate the members of the top-level object. `None` if the document is not an object.
+fn scan_members(content: &str) -> Option<Layout> {
+    let bytes = content.as_bytes();
+    let mut layout = Layout { members: Vec::new(), has_comments: false };
+    let mut depth = 0;
+    let mut key: Option<(String, usize)> = None;
+    let mut awaiting_value = false;
+    // Member whose value is being read, and where its last token ended
+    let mut current: Option<Member> = None;
+    let mut last_end = 0;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let c = bytes[i];
+        let comment = c == b'/' && matches!(bytes.get(i + 1), Some(b'/') | Some(b'*'));
+        if c.is_ascii_whitespace() {
+            i += 1;
+            continue;
+        }
+        if comment {
+            layout.has_comments = true;
+            if let Some(member) = current.as_mut() {
+                member.has_comment = true;
+            }
+            i = if bytes[i + 1] == b'/' {
+                content[i..].find('\n').map_or(bytes.len(), |n| i + n)
+            } else {
+                content[i + 2..].find("*/").map_or(bytes.len(), |n| i + n + 4)
+            };
+            continue;
+        }
+        if depth == 0 && c != b'{' {
+            return None;
+        }
+        if awaiting_value {
+            awaiting_value = false;
+            let (key, key_start) = key.take()?;
+            current = Some(Member { key, key_start, value: i..i, has_comment: false });
+        }
+
+        match c {
+            b'"' => {
+                let start = i;
+                i += 1;
+                while i < bytes.len() && bytes[i] != b'"' {
+                    i += if bytes[i] == b'\\' { 2 } else { 1 };
+                }
+                i = (i + 1).min(bytes.len());
+                if depth == 1 && current.is_none() {
+                    key = Some((serde_json::from_str(&content[start..i]).ok()?, start));
+                }
+                last_end = i;
+                continue;
+            }
+            b':' if depth == 1 && current.is_none() => awaiting_value = true,
+            b',' if depth == 1 => {
+                if let Some(mut member) = current.take() {
+                    member.value.end = last_end;
+                    layout.members.push(member);
+                }
+            }
+            b'{' | b'[' => depth += 1,
+            b'}' | b']' => {
+                depth -= 1;
+                if depth == 0 {
+                    if let Some(mut member) = current.take() {
+                        member.value.end = last_end;
+                        layout.members.push(member);
+                    }
+                    return Some(layout);
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+        last_end = i;
+    }
+    None
+}
+
+/// Remove `//` and `/* */` comments and trailing commas, leaving strings untouched
+fn strip_jsonc(content: &str) -> String {
+    let chars: Vec<char> = content.chars().collect();
+    let mut out = String::with_capacity(content.len());
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            '"' => {
+                out.push(c);
+                i += 1;
+                while i < chars.len() {
+                    out.push(chars[i]);
+                    if chars[i] == '\\' && i + 1 < chars.len() {
+                        out.push(chars[i + 1]);
+                        i += 2;
+                        continue;
+                    }
+                    i += 1;
+                    if chars[i - 1] == '"' {
+                        break;
+                    }
+                }
+            }
+            '/' if chars.get(i + 1) == Some(&'/') => {
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+            }
+            '/' if chars.get(i + 1) == Some(&'*') => {
+                i += 2;
+                while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                    i += 1;
+                }
+                i += 2;
+            }
+            ',' => {
+                // Drop the comma if the next significant character closes a container
+                let mut j = i + 1;
+                loop {
+                    while j < chars.len() && chars[j].is_whitespace() {
+                        j += 1;
+                    }
+                    if chars.get(j) == Some(&'/') && chars.get(j + 1) == Some(&'/') {
+                        while j < chars.len() && chars[j] != '\n' {
+                            j += 1;
+                        }
+                    } else if chars.get(j) == Some(&'/') && chars.get(j + 1) == Some(&'*') {
+                        j += 2;
+                        while j < chars.len() && !(chars[j] == '*' && chars.get(j + 1) == Some(&'/')) {
+                            j += 1;
+                        }
+                        j += 2;
+                    } else {
+                        break;
+                    }
+                }
+                if !matches!(chars.get(j), Some('}') | Some(']')) {
+                    out.push(c);
+                }
+                i += 1;
+            }
+            _ => {
+                out.push(c);
+                i += 1;
+            }
+        }
+    }
+
+    out
+}
+
+/// Get (creating if needed) an array stored under `key` of the root object
+fn array_mut<'a>(root: &'a mut Value, key: &str) -> Result<&'a mut Vec<Value>> {
+    let object = root.as_object_mut()
+        .context("Expected a JSON object at the top level")?;
+
+    let value = object.entry(key.to_string())
+        .or_insert_with(|| Value::Array(Vec::new()));
+
+    value.as_array_mut()
+        .with_context(|| format!("'{}' is not an array", key))
+}
+
+fn continue_rule_name(value: &Value) -> Option<&str> {
+    value.get("name").and_then(Value::as_str)
+}
+
+/// Add or replace the Continue rule `skillctl:<skill>` pointing at a skill file
+pub fn continue_set_skill(root: &mut Value, skill_name: &str, skill_path: &str) -> Result<()> {
+    let rule = json!({
+        "name": format!("{}{}", CONTINUE_RULE_PREFIX, skill_name),
+        "rule": format!("Read and follow the skill instructions in {}", skill_path),
+    });
+    upsert_continue_rule(root, rule)
+}
+
+/// Remove the Continue rule for a skill, returning whether it was present
+pub fn continue_remove_skill(root: &mut Value, skill_name: &str) -> Result<bool> {
+    let name = format!("{}{}", CONTINUE_RULE_PREFIX, skill_name);
+    remove_continue_rule(root, &name)
+}
+
+/// Replace the Continue rule carrying the memory context
+pub fn continue_set_memory(root: &mut Value, memory: &str) -> Result<()> {
+    if memory.trim().is_empty() {
+        remove_continue_rule(root, CONTINUE_MEMORY_RULE)?;
+        return Ok(());
+    }
+    let rule = json!({
+        "name": CONTINUE_MEMORY_RULE,
+        "rule": memory.trim(),
+    });
+    upsert_continue_rule(root, rule)
+}
+
+/// Skills referenced by skillctl rules in a Continue config
+pub fn continue_skill_names(root: &Value) -> Vec<String> {
+    root.get("rules")
+        .and_then(Value::as_array)
+        .map(|rules| rules.iter()
+            .filter_map(continue_rule_name)
+            .filter(|name| *name != CONTINUE_MEMORY_RULE)
+            .filter_map(|name| name.strip_prefix(CONTINUE_RULE_PREFIX))
+            .map(|name| name.to_string())
+            .collect())
+        .unwrap_or_default()
+}
+
+fn upsert_continue_rule(root: &mut Value, rule: Value) -> Result<()> {
+    let name = continue_rule_name(&rule).unwrap_or_default().to_string();
+    let rules = array_mut(root, "rules")?;
+
+    match rules.iter_mut().find(|r| continue_rule_name(r) == Some(name.as_str())) {
+        Some(existing) => *existing = rule,
+        None => rules.push(rule),
+    }
+    Ok(())
+}
+
+fn remove_continue_rule(root: &mut Value, name: &str) -> Result<bool> {
+    if root.get("rules").is_none() {
+        return Ok(false);
+    }
+    let rules = array_mut(root, "rules")?;
+    let before = rules.len();
+    rules.retain(|r| continue_rule_name(r) != Some(name));
+    Ok(rules.len() != before)
+}
+
+fn instruction_file(value: &Value) -> Option<&str> {
+    value.get("file").and_then(Value::as_str)
+}
+
+/// Skill name of a Copilot instruction file pointing at `<store>/<name>/SKILL.md`.
+/// Files `owned` does not vouch for belong to the user, whatever their name.
+fn skill_name_of_file<'a>(file: &'a str, owned: &impl Fn(&str) -> bool) -> Option<&'a str> {
+    let parent = file.strip_suffix("/SKILL.md")?;
+    parent.rsplit('/').next()
+        .filter(|name| !name.is_empty())
+        .filter(|_| owned(file))
+}
+
+/// Add a Copilot instruction file entry for a skill
+pub fn vscode_set_skill(root: &mut Value, skill_name: &str, skill_path: &str, owned: impl Fn(&str) -> bool) -> Result<()> {
+    let instructions = array_mut(root, COPILOT_INSTRUCTIONS_KEY)?;
+    instructions.retain(|i| instruction_file(i).and_then(|f| skill_name_of_file(f, &owned)) != Some(skill_name));
+    instructions.push(json!({ "file": skill_path }));
+    Ok(())
+}
+
+/// Remove the Copilot instruction file entry for a skill
+pub fn vscode_remove_skill(root: &mut Value, skill_name: &str, owned: impl Fn(&str) -> bool) -> Result<bool> {
+    if root.get(COPILOT_INSTRUCTIONS_KEY).is_none() {
+        return Ok(false);
+    }
+    let instructions = array_mut(root, COPILOT_INSTRUCTIONS_KEY)?;
+    let before = instructions.len();
+    instructions.retain(|i| instruction_file(i).and_then(|f| skill_name_of_file(f, &owned)) != Some(skill_name));
+    Ok(instructions.len() != before)
+}
+
+/// Ensure a Copilot instruction file entry exists (or is removed) for a plain file
+pub fn vscode_set_file(root: &mut Value, file: &str, present: bool) -> Result<()> {
+    if !present && root.get(COPILOT_INSTRUCTIONS_KEY).is_none() {
+        return Ok(());
+    }
+    let instructions = array_mut(root, COPILOT_INSTRUCTIONS_KEY)?;
+    let exists = instructions.iter().any(|i| instruction_file(i) == Some(file));
+    if present && !exists {
+        instructions.push(json!({ "file": file }));
+    } else if !present {
+        instructions.retain(|i| instruction_file(i) != Some(file));
+    }
+    Ok(())
+}
+
+/// Skills referenced by Copilot instruction files in VS Code settings
+pub fn vscode_skill_names(root: &Value, owned: impl Fn(&str) -> bool) -> Vec<String> {
+    root.get(COPILOT_INSTRUCTIONS_KEY)
+        .and_then(Value::as_array)
+        .map(|instructions| instructions.iter()
+            .filter_map(instruction_file)
+            .filter_map(|file| skill_name_of_file(file, &owned))
+            .map(|name| name.to_string())
+            .collect())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VSCODE_SETTINGS: &str = r#"{
+    // Editor settings
+    "editor.tabSize": 2,
+    "files.exclude": { "**/target": true, },
+    /* Copilot */
+    "github.copilot.chat.codeGeneration.instructions": [
+        { "text": "Prefer small functions // not a comment" },
+    ],
+}
+"#;
+
+    #[test]
+    fn test_parse_jsonc() {
+        let value = parse_jsonc(VSCODE_SETTINGS).unwrap();
+        assert_eq!(value["editor.tabSize"], 2);
+        assert_eq!(value["files.exclude"]["**/target"], true);
+        assert_eq!(
+            value[COPILOT_INSTRUCTIONS_KEY][0]["text"],
+            "Prefer small functions // not a comment"
+        );
+
+        assert!(parse_jsonc("").unwrap().as_object().unwrap().is_empty());
+        assert!(parse_jsonc("[1, 2]").is_err());
+        assert!(parse_jsonc("{ \"a\": }").is_err());
+    }
+
+    #[test]
+    fn test_vscode_skill_roundtrip() {
+        let mut root = parse_jsonc(VSCODE_SETTINGS).unwrap();
+        let owned = |file: &str| file.starts_with(".skillctl/store/");
+
+        vscode_set_skill(&mut root, "python", ".skillctl/store/python/SKILL.md", owned).unwrap();
+        vscode_set_skill(&mut root, "python", ".skillctl/store/python/SKILL.md", owned).unwrap();
+        vscode_set_file(&mut root, ".vscode/skillctl-memory.md", true).unwrap();
+        assert_eq!(vscode_skill_names(&root, owned), vec!["python"]);
+
+        // Output is valid JSON and keeps unrelated settings in their original order
+        let written = to_pretty_json(&root).unwrap();
+        let reparsed: Value = serde_json::from_str(&written).unwrap();
+        let keys: Vec<&String> = reparsed.as_object().unwrap().keys().collect();
+        assert_eq!(keys, vec!["editor.tabSize", "files.exclude", COPILOT_INSTRUCTIONS_KEY]);
+        assert_eq!(reparsed[COPILOT_INSTRUCTIONS_KEY].as_array().unwrap().len(), 3);
+
+        assert!(vscode_remove_skill(&mut root, "python", owned).unwrap());
+        vscode_set_file(&mut root, ".vscode/skillctl-memory.md", false).unwrap();
+        assert!(vscode_skill_names(&root, owned).is_empty());
+        assert_eq!(root[COPILOT_INSTRUCTIONS_KEY].as_array().unwrap().len(), 1);
+        assert_eq!(root["editor.tabSize"], 2);
+
+        // The user's own instruction files are never claimed, even under a skill's name
+        vscode_set_file(&mut root, "docs/python/SKILL.md", true).unwrap();
+        vscode_set_skill(&mut root, "python", ".skillctl/store/python/SKILL.md", owned).unwrap();
+        assert_eq!(vscode_skill_names(&root, owned), vec!["python"]);
+        assert!(vscode_remove_skill(&mut root, "python", owned).unwrap());
+        assert!(!vscode_remove_skill(&mut root, "python", owned).unwrap());
+        assert_eq!(root[COPILOT_INSTRUCTIONS_KEY][1]["file"], "docs/python/SKILL.md");
+    }
+
+    #[test]
+    fn test_update_jsonc_keeps_comments() {
+        let owned = |file: &str| file.starts_with(".skillctl/store/");
+        let mut root = parse_jsonc(VSCODE_SETTINGS).unwrap();
+        vscode_set_skill(&mut root, "python", ".skillctl/store/python/SKILL.md", owned).unwrap();
+
+        // Only the changed setting is rewritten, in place
+        let written = update_jsonc(VSCODE_SETTINGS, &root).unwrap();
+        assert_eq!(parse_jsonc(&written).unwrap(), root);
+        assert!(written.starts_with("{\n    // Editor settings\n    \"editor.tabSize\": 2,\n"));
+        assert!(written.contains("    /* Copilot */\n"));
+        assert!(written.contains("\n        {\n            \"file\": \".skillctl/store/python/SKILL.md\"\n        }\n    ],\n}\n"));
+
+        // New settings are appended after the last one
+        let mut root = parse_jsonc(&written).unwrap();
+        continue_set_skill(&mut root, "rust", ".skillctl/store/rust/SKILL.md").unwrap();
+        let appended = update_jsonc(&written, &root).unwrap();
+        assert_eq!(parse_jsonc(&appended).unwrap(), root);
+        assert!(appended.contains("// Editor settings"));
+
+        let mut root = parse_jsonc("{ // nothing yet\n}").unwrap();
+        continue_set_memory(&mut root, "- Uses Rust").unwrap();
+        let written = update_jsonc("{ // nothing yet\n}", &root).unwrap();
+        assert_eq!(parse_jsonc(&written).unwrap(), root);
+        assert!(written.contains("// nothing yet"));
+
+        // Comments inside a value that changes would be lost, so nothing is written
+        let commented = "{\n  // Rules\n  \"rules\": [\n    \"Be brief\" // mine\n  ]\n}\n";
+        let mut root = parse_jsonc(commented).unwrap();
+        continue_set_skill(&mut root, "rust", ".skillctl/store/rust/SKILL.md").unwrap();
+        assert!(update_jsonc(commented, &root).is_err());
+
+        // Plain JSON is pretty-printed as before
+        let root = parse_jsonc("{\"a\":1}").unwrap();
+        assert_eq!(update_jsonc("{\"a\":1}", &root).unwrap(), "{\n  \"a\": 1\n}\n");
+    }
+
+    #[test]
+    fn test_continue_rules_roundtrip() {
+        let mut root = parse_jsonc(r#"{"models": [{"title": "GPT"}], "rules": ["Always write tests"]}"#).unwrap();
+
+        continue_set_skill(&mut root, "rust", ".skillctl/store/rust/SKILL.md").unwrap();
+        continue_set_memory(&mut root, "\n# 🧠 Active Memory Context\n\nNo memories stored yet.\n").unwrap();
+        continue_set_memory(&mut root, "\n# 🧠 Active Memory Context\n\n- Uses Rust\n").unwrap();
+        assert_eq!(continue_skill_names(&root), vec!["rust"]);
+
+        let rules = root["rules"].as_array().unwrap();
+        assert_eq!(rules.len(), 3);
+        assert_eq!(rules[0], "Always write tests");
+        assert!(rules[2]["rule"].as_str().unwrap().contains("Uses Rust"));
+
+        assert!(continue_remove_skill(&mut root, "rust").unwrap());
+        continue_set_memory(&mut root, "").unwrap();
+        assert_eq!(root["rules"].as_array().unwrap().len(), 1);
+        assert_eq!(root["models"][0]["title"], "GPT");
+    }
+}
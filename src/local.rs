@@ -0,0 +1,149 @@
+//! Skills installed from a directory on this machine (`./path/to/skill`, `file://`)
+
+use anyhow::{Result, Context, bail};
+use std::path::{Path, PathBuf};
+use url::Url;
+use crate::network::SKILL_LAYOUTS;
+use crate::security::validate_skill_name;
+use crate::store::{SkillFile, collect_files};
+
+/// The directory named by an `add` source, if it is a local one: a `file://` URL or a
+/// path starting with `.`, `/` or `~/`. Relative paths are taken from the current directory.
+pub fn local_source_path(spec: &str) -> Option<Result<PathBuf>> {
+    if spec.starts_with("file://") {
+        let path = Url::parse(spec).ok()
+            .and_then(|url| url.to_file_path().ok())
+            .with_context(|| format!("Invalid file URL: {}", spec));
+        return Some(path);
+    }
+
+    if let Some(rest) = spec.strip_prefix("~/") {
+        let path = dirs::home_dir()
+            .map(|home| home.join(rest))
+            .context("Could not determine the home directory");
+        return Some(path);
+    }
+
+    let path = Path::new(spec);
+    if path.is_absolute() || spec == "." || spec == ".." || spec.starts_with("./") || spec.starts_with("../") {
+        return Some(Ok(path.to_path_buf()));
+    }
+    None
+}
+
+/// Locate the skill directory under a local source. `root` is either the skill
+/// itself (it holds a SKILL.md) or a checkout searched for `skill_name` under
+/// `custom_path` or the usual layouts. Returns the canonical directory.
+pub fn find_local_skill(root: &Path, skill_name: Option<&str>, custom_path: Option<&str>) -> Result<PathBuf> {
+    let root = root.canonicalize()
+        .with_context(|| format!("Local skill source not found: {}", root.display()))?;
+    if !root.is_dir() {
+        bail!("Local skill source is not a directory: {}", root.display());
+    }
+    if root.join("SKILL.md").is_file() {
+        return Ok(root);
+    }
+
+    let Some(skill_name) = skill_name else {
+        bail!("No SKILL.md in {}. Point at a skill directory or pass --skill <name>.", root.display());
+    };
+    validate_skill_name(skill_name)?;
+
+    let mut candidates = Vec::new();
+    if let Some(custom_path) = custom_path {
+        let custom = root.join(custom_path.trim_end_matches("SKILL.md").trim_end_matches('/'));
+        candidates.push(custom.join(skill_name));
+        candidates.push(custom);
+    }
+    candidates.push(root.join(skill_name));
+    candidates.extend(SKILL_LAYOUTS.iter()
+        .filter(|layout| !layout.contains('*'))
+        .map(|layout| root.join(layout).join(skill_name)));
+
+    let found = candidates.into_iter()
+        .find(|dir| dir.join("SKILL.md").is_file())
+        .with_context(|| format!("Skill '{}' not found in {}", skill_name, root.display()))?;
+
+    // The skill must not escape the source through `..` or a symlink
+    let found = found.canonicalize()?;
+    if !found.starts_with(&root) {
+        bail!("Path traversal detected: skill is outside {}", root.display());
+    }
+    Ok(found)
+}
+
+/// Read every file of a local skill directory. Symlinks are skipped.
+pub fn read_local_skill(dir: &Path) -> Result<Vec<SkillFile>> {
+    let mut files = Vec::new();
+    collect_files(dir, dir, &mut files)
+        .with_context(|| format!("Failed to read local skill {}", dir.display()))?;
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(files)
+}
+
+/// The `file://` URL recorded as the source of a local skill
+pub fn source_url(dir: &Path) -> Result<String> {
+    Url::from_directory_path(dir)
+        .map(|url| url.as_str().trim_end_matches('/').to_string())
+        .map_err(|_| anyhow::anyhow!("Local skill path must be absolute: {}", dir.display()))
+}
+
+/// The directory of a skill installed from a local source, or `None` for remote sources
+pub fn source_dir(source: &str) -> Option<PathBuf> {
+    if !source.starts_with("file://") {
+        return None;
+    }
+    Url::parse(source).ok()?.to_file_path().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const SKILL: &str = "---\ndescription: Local skill\n---\n# Local\n";
+
+    #[test]
+    fn test_local_source_path() {
+        assert_eq!(local_source_path("./skills/demo").unwrap().unwrap(), PathBuf::from("./skills/demo"));
+        assert_eq!(local_source_path("/opt/skills/demo").unwrap().unwrap(), PathBuf::from("/opt/skills/demo"));
+        assert_eq!(local_source_path("file:///opt/skills/demo").unwrap().unwrap(), PathBuf::from("/opt/skills/demo"));
+        assert!(local_source_path("file://host/share").unwrap().is_err());
+
+        // Repository URLs and qualified ids are not local
+        assert!(local_source_path("https://github.com/user/repo").is_none());
+        assert!(local_source_path("user/repo:skill").is_none());
+        assert!(local_source_path("user/repo").is_none());
+    }
+
+    #[test]
+    fn test_find_local_skill() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().canonicalize().unwrap();
+        std::fs::create_dir_all(root.join("skills/demo")).unwrap();
+        std::fs::write(root.join("skills/demo/SKILL.md"), SKILL).unwrap();
+
+        // A skill directory, or a checkout searched by name
+        let skill_dir = root.join("skills/demo");
+        assert_eq!(find_local_skill(&skill_dir, None, None).unwrap(), skill_dir);
+        assert_eq!(find_local_skill(&root, Some("demo"), None).unwrap(), skill_dir);
+        assert_eq!(find_local_skill(&root, Some("demo"), Some("skills")).unwrap(), skill_dir);
+
+        assert!(find_local_skill(&root, None, None).is_err());
+        assert!(find_local_skill(&root, Some("missing"), None).is_err());
+        assert!(find_local_skill(&root, Some("../demo"), None).is_err());
+        assert!(find_local_skill(&root.join("nowhere"), None, None).is_err());
+    }
+
+    #[test]
+    fn test_source_url_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path().canonicalize().unwrap().join("my skill");
+
+        let url = source_url(&dir).unwrap();
+        assert!(url.starts_with("file:///"));
+        assert!(url.ends_with("/my%20skill"));
+        assert_eq!(source_dir(&url).unwrap(), dir);
+        assert!(source_dir("https://github.com/user/repo").is_none());
+    }
+}
@@ -0,0 +1,121 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use anyhow::{Result, Context, bail};
+use crate::scope::Scope;
+
+pub const LOCKFILE_PATH: &str = "skills.lock";
+const LOCKFILE_VERSION: u32 = 1;
+
+/// Pinned revisions of every installed skill (skills.lock)
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct Lockfile {
+    pub version: u32,
+    pub skills: BTreeMap<String, LockedSkill>,
+}
+
+/// Exact location and content hash of one installed skill
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct LockedSkill {
+    /// Repository URL the skill was added from
+    pub source: String,
+    /// Commit SHA the skill was resolved to (branch name if it could not be resolved)
+    pub resolved_ref: String,
+    /// Path of SKILL.md inside the repository
+    pub path: String,
+    /// Raw URL of SKILL.md at `resolved_ref`
+    pub raw_url: String,
+    /// SHA256 of the SKILL.md content, or aggregate hash of all files
+    pub hash: String,
+    /// Per-file SHA256 hashes when the skill ships supporting files
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub files: BTreeMap<String, String>,
+}
+
+impl Default for Lockfile {
+    fn default() -> Self {
+        Self {
+            version: LOCKFILE_VERSION,
+            skills: BTreeMap::new(),
+        }
+    }
+}
+
+impl Lockfile {
+    /// Load a lockfile, returning an empty one if it does not exist
+    pub fn load_from(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(path)
+            .context("Failed to read skills.lock")?;
+
+        let lockfile: Lockfile = serde_json::from_str(&content)
+            .context("Failed to parse skills.lock. The file may be corrupted.")?;
+
+        if lockfile.version > LOCKFILE_VERSION {
+            bail!("skills.lock version {} is newer than this skillctl supports ({})", lockfile.version, LOCKFILE_VERSION);
+        }
+
+        Ok(lockfile)
+    }
+
+    pub fn save_to(&self, path: impl AsRef<Path>) -> Result<()> {
+        let json = serde_json::to_string_pretty(self)
+            .context("Failed to serialize skills.lock")?;
+
+        fs::write(path, json + "\n")
+            .context("Failed to write skills.lock")?;
+
+        Ok(())
+    }
+}
+
+pub fn load_lockfile(scope: &Scope) -> Result<Lockfile> {
+    Lockfile::load_from(scope.lockfile_path())
+}
+
+pub fn save_lockfile(scope: &Scope, lockfile: &Lockfile) -> Result<()> {
+    lockfile.save_to(scope.lockfile_path())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_lockfile_roundtrip() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("skills.lock");
+
+        // Missing lockfile loads as empty
+        let mut lockfile = Lockfile::load_from(&path).unwrap();
+        assert!(lockfile.skills.is_empty());
+
+        lockfile.skills.insert("memory".to_string(), LockedSkill {
+            source: "https://github.com/user/repo".to_string(),
+            resolved_ref: "0123456789abcdef0123456789abcdef01234567".to_string(),
+            path: "skills/memory/SKILL.md".to_string(),
+            raw_url: "https://raw.githubusercontent.com/user/repo/0123456789abcdef0123456789abcdef01234567/skills/memory/SKILL.md".to_string(),
+            hash: "abc".to_string(),
+            files: BTreeMap::new(),
+        });
+        lockfile.save_to(&path).unwrap();
+
+        let loaded = Lockfile::load_from(&path).unwrap();
+        assert_eq!(loaded, lockfile);
+    }
+
+    #[test]
+    fn test_lockfile_rejects_newer_version() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("skills.lock");
+        fs::write(&path, r#"{"version": 99, "skills": {}}"#).unwrap();
+
+        assert!(Lockfile::load_from(&path).is_err());
+    }
+}
@@ -4,51 +4,102 @@ mod editors;
 mod network;
 mod store;
 mod memory;
+mod lockfile;
+mod prompt;
+mod manifest;
+mod managed;
+mod json_config;
+mod adapters;
+mod scope;
+mod providers;
+mod local;
+mod auth;
+#[cfg(test)]
+mod mock_server;
 
 use anyhow::{Result, Context};
 use clap::Parser;
-use std::path::Path;
 use std::fs;
-use cliclack::{intro, outro, log, spinner, confirm, outro_note};
+use std::path::{Path, PathBuf};
+use cliclack::{intro, outro, log, spinner, outro_note};
 use console::style;
 
-use cli::{Cli, Commands, MemoryCommands};
-use editors::{EditorType, SkillConfig, default_store_path, load_config, save_config, detect_installed_editors, inject_reference, remove_reference, inject_memory_context};
-use network::SecureHttpClient;
-use store::{SkillStore, update_skill_in_config, remove_skill_from_config};
+use cli::{Cli, Commands, MemoryCommands, StoreCommands, AuthCommands, OutputFormat};
+use editors::{EditorType, SkillConfig, SkillEntry, load_config, save_config, detect_installed_editors, inject_reference, remove_reference, list_references, is_delivery_stale};
+use network::{SecureHttpClient, FetchedSkill, parse_qualified_id, split_repo_ref};
+use providers::TrustedHosts;
+use store::{SkillStore, BlobStore, SkillFile, update_skill_in_config, remove_skill_from_config};
 use security::validate_skill_name;
 use memory::{MemoryStore, MemoryTag};
+use lockfile::{Lockfile, LockedSkill, load_lockfile, save_lockfile};
+use prompt::Interaction;
+use scope::Scope;
+use adapters::EditorAdapter;
+use manifest::SkillManifest;
+use auth::Credentials;
 use std::str::FromStr;
 
 fn main() -> Result<()> {
     let cli = Cli::parse();
+    let ui = Interaction::from_flags(cli.yes, cli.non_interactive);
+
+    let format = cli.format;
+    let scope = if cli.global { Scope::global()? } else { Scope::Project };
+
+    // Keep stdout clean for machine-readable output
+    if format == OutputFormat::Text {
+        intro(format!(
+            "{} {} {}",
+            style("skillctl").cyan().bold(),
+            style(env!("CARGO_PKG_VERSION")).dim(),
+            "Launch sequence initiated."
+        ))?;
 
-    intro(format!(
-        "{} {} {}",
-        style("skillctl").cyan().bold(),
-        style(env!("CARGO_PKG_VERSION")).dim(),
-        "Launch sequence initiated."
-    ))?;
-
-    log::step(format!("{} Time to build intelligent agents.", 
-        style("◠ ◡ ◠").cyan()))?;
+        log::step(format!("{} Time to build intelligent agents.", 
+            style("◠ ◡ ◠").cyan()))?;
+    }
 
     match &cli.command {
-        Commands::Init => cmd_init()?,
-        Commands::Add { url, skill, path, list } => {
+        Commands::Init { editors } => cmd_init(&ui, &scope, editors)?,
+        Commands::Add { url, skill, path, list, reference, alias, dev } => if let Some(dir) = local::local_source_path(url) {
+            if *list || reference.is_some() {
+                anyhow::bail!("--list and --ref only apply to repositories, not local skill directories");
+            }
+            cmd_add_local(&ui, &scope, &dir?, skill.as_deref(), path.as_deref(), alias.as_deref(), *dev)?;
+        } else {
+            if *dev {
+                anyhow::bail!("--dev only applies to local skill directories (./path/to/skill or file://)");
+            }
+
+            // `owner/repo:skill` names both the repository and the skill
+            let (url, skill) = match (parse_qualified_id(url), skill) {
+                (Some((repo_url, skill_name)), None) => (repo_url, Some(skill_name)),
+                _ => (url.clone(), skill.clone()),
+            };
+            let (url, reference) = match (split_repo_ref(&url), reference) {
+                ((_, Some(in_url)), Some(flag)) if in_url != *flag => {
+                    anyhow::bail!("Conflicting refs: '{}' in the URL and --ref {}", in_url, flag);
+                }
+                ((url, in_url), flag) => (url, in_url.or(flag.clone())),
+            };
             if *list {
-                cmd_list_available(url, path.clone())?;
+                cmd_list_available(&scope, &url, path.clone(), reference.as_deref())?;
             } else if let Some(skill_name) = skill {
-                cmd_add(url, skill_name, path.clone())?;
+                cmd_add(&ui, &scope, &url, &skill_name, path.clone(), alias.as_deref(), reference.as_deref())?;
             } else {
                 log::error("--skill <name> is required when not using --list")?;
             }
         },
-        Commands::Remove { skills } => cmd_remove(skills)?,
-        Commands::Install => cmd_install()?,
-        Commands::Search => cmd_search()?,
-        Commands::List => cmd_list()?,
-        Commands::Memory(subcommand) => cmd_memory(subcommand)?,
+        Commands::Remove { skills } => cmd_remove(&scope, skills)?,
+        Commands::Update { skills } => cmd_update(&ui, &scope, skills)?,
+        Commands::Outdated { skills } => cmd_outdated(&scope, skills)?,
+        Commands::Install => cmd_install(&scope)?,
+        Commands::Verify { fix } => cmd_verify(&scope, *fix)?,
+        Commands::Search => cmd_search(&ui, &scope, format)?,
+        Commands::List => cmd_list(&scope, format)?,
+        Commands::Memory(subcommand) => cmd_memory(&scope, subcommand, format)?,
+        Commands::Store(subcommand) => cmd_store(subcommand, format)?,
+        Commands::Auth(subcommand) => cmd_auth(&ui, subcommand, format)?,
     }
 
     Ok(())
@@ -58,10 +109,19 @@ fn main() -> Result<()> {
 // COMMAND: INIT
 // ============================================================================
 
-fn cmd_init() -> Result<()> {
-    if Path::new("skills.json").exists() {
-        log::warning("skills.json already exists.")?;
-        let overwrite = confirm("Do you want to re-initialize? (This will overwrite skills.json)").interact()?;
+fn cmd_init(ui: &Interaction, scope: &Scope, editor_names: &[String]) -> Result<()> {
+    // Parse --editors up front so a typo fails before anything is touched
+    let requested_editors = editor_names.iter()
+        .map(|name| EditorType::from_str(name.trim()))
+        .collect::<Result<Vec<_>>>()?;
+
+    if let Some(editor) = requested_editors.iter().find(|e| scope.adapter(e).is_none()) {
+        anyhow::bail!("{} has no user-level location; add it to a project instead of --global", editor);
+    }
+
+    if scope.config_path().exists() {
+        log::warning(format!("{} already exists.", scope.config_path().display()))?;
+        let overwrite = ui.confirm("Do you want to re-initialize? (This will overwrite skills.json)")?;
         if !overwrite {
             outro("Skipping init.")?;
             return Ok(());
@@ -71,22 +131,24 @@ fn cmd_init() -> Result<()> {
     log::info("Initializing secure skill environment.")?;
 
     // Auto-detect installed editors
-    let detected_editors = detect_installed_editors();
+    let detected_editors = detect_installed_editors(scope);
     
-    let selected_editors = if !detected_editors.is_empty() {
+    let selected_editors = if !requested_editors.is_empty() {
+        requested_editors
+    } else if !detected_editors.is_empty() {
         log::info(format!("Detected {} editor(s): {:?}", 
             detected_editors.len(), 
             detected_editors.iter().map(|e| e.to_string()).collect::<Vec<_>>()
         ))?;
         
-        let use_detected = confirm("Use detected editors?").interact()?;
+        let use_detected = ui.confirm("Use detected editors?")?;
         if use_detected {
             detected_editors
         } else {
-            prompt_editor_selection()?
+            prompt_editor_selection(ui, scope)?
         }
     } else {
-        prompt_editor_selection()?
+        prompt_editor_selection(ui, scope)?
     };
 
     if selected_editors.is_empty() {
@@ -96,38 +158,47 @@ fn cmd_init() -> Result<()> {
 
     let config = SkillConfig {
         active_editors: selected_editors.clone(),
-        store_path: default_store_path(),
+        store_path: scope.default_store_path(),
         skills: std::collections::HashMap::new(),
+        rule_overrides: std::collections::HashMap::new(),
+        delivery: std::collections::HashMap::new(),
+        adapters: Vec::new(),
+        trusted_hosts: TrustedHosts::new(),
+        inherit_global: true,
+        scope: scope.clone(),
     };
 
     let spin = spinner();
     spin.start("Scaffolding directories...");
     
     save_config(&config)?;
+    save_lockfile(scope, &Lockfile::default())?;
     fs::create_dir_all(&config.store_path)?;
 
-    for editor in &selected_editors {
-        let rules_file = editor.config_file();
+    // User-level editor files are created on demand, not scaffolded
+    for editor in selected_editors.iter().filter(|_| !scope.is_global()) {
+        let adapter = editor.adapter();
+        let rules_file = adapter.config_file();
         if let Some(parent) = rules_file.parent() { 
             fs::create_dir_all(parent)?; 
         }
         
         if !rules_file.exists() {
-            fs::write(&rules_file, format!("# AI Rules for {}\n", editor))?;
+            fs::write(&rules_file, adapter.initial_config())?;
         }
         
-        fs::create_dir_all(editor.skills_dir())?;
+        fs::create_dir_all(adapter.skills_dir())?;
     }
 
     spin.stop("Environment ready.");
     
     // Inject memory instructions into all editors
-    let mut memory_store = MemoryStore::new(&config.store_path)?;
+    let memory_store = MemoryStore::new(&config.store_path)?;
     let memory_context = memory_store.to_context_string();
     
-    for editor in &selected_editors {
-        if let Err(e) = inject_memory_context(editor, &memory_context) {
-            log::warning(format!("Could not inject memory context for {}: {}", editor, e))?;
+    for adapter in config.active_adapters()? {
+        if let Err(e) = adapter.inject_memory(&memory_context) {
+            log::warning(format!("Could not inject memory context for {}: {}", adapter.name(), e))?;
         }
     }
     
@@ -139,7 +210,7 @@ fn cmd_init() -> Result<()> {
     let memory_repo = "https://github.com/joeldevz/agent-skill";
     let memory_skill = "memory";
     
-    match cmd_add(memory_repo, memory_skill, None) {
+    match cmd_add(ui, scope, memory_repo, memory_skill, None, None, None) {
         Ok(_) => spin.stop("Memory skill installed."),
         Err(e) => {
             log::warning(format!("Memory skill auto-install skipped: {}", e))?;
@@ -155,12 +226,13 @@ fn cmd_init() -> Result<()> {
     Ok(())
 }
 
-fn prompt_editor_selection() -> Result<Vec<EditorType>> {
+fn prompt_editor_selection(ui: &Interaction, scope: &Scope) -> Result<Vec<EditorType>> {
     use strum::IntoEnumIterator;
+
+    ui.require_tty("Editor selection", "pass --editors cursor,claude-code,...")?;
     
-    let editors: Vec<EditorType> = EditorType::iter().collect();
-    let items: Vec<(EditorType, String, String)> = editors.iter()
-        .map(|e| (e.clone(), e.to_string(), format!("Uses {}", e.skills_dir().display())))
+    let items: Vec<(EditorType, String, String)> = EditorType::iter()
+        .filter_map(|e| scope.adapter(&e).map(|adapter| (e.clone(), e.to_string(), format!("Uses {}", adapter.skills_dir().display()))))
         .collect();
 
     let selected_editors: Vec<EditorType> = cliclack::multiselect("Which AI Editors are you using?")
@@ -174,33 +246,66 @@ fn prompt_editor_selection() -> Result<Vec<EditorType>> {
 // COMMAND: ADD
 // ============================================================================
 
-fn cmd_add(repo_url: &str, skill_name: &str, custom_path: Option<String>) -> Result<()> {
+fn cmd_add(ui: &Interaction, scope: &Scope, repo_url: &str, skill_name: &str, custom_path: Option<String>, alias: Option<&str>, reference: Option<&str>) -> Result<()> {
     // Validate skill name (security)
     validate_skill_name(skill_name)
         .context("Invalid skill name")?;
+    let installed_name = alias.unwrap_or(skill_name);
+    validate_skill_name(installed_name)
+        .context("Invalid alias")?;
 
-    let mut config = load_config()
+    let mut config = load_config(scope)
         .context("Please run 'skillctl init' first.")?;
+
+    // A name belongs to one source; another repo's skill needs an alias
+    config.check_name_conflict(installed_name, repo_url, skill_name)?;
     
     let spin = spinner();
     spin.start(format!("Fetching {}...", skill_name));
 
     // Create secure HTTP client
-    let client = SecureHttpClient::new()?;
+    let client = http_client(&config)?;
+
+    // A requested branch or tag is resolved up front so everything is read at one commit
+    let commit = match reference {
+        Some(reference) => match client.resolve_commit(repo_url, reference) {
+            Ok(sha) => Some(sha),
+            Err(e) => {
+                spin.stop("Unknown ref.");
+                return Err(e);
+            }
+        },
+        None => None,
+    };
 
     // Try to find and download the skill
-    let (content, _path) = client.find_skill(repo_url, skill_name, custom_path)
+    let fetched = client.find_skill(repo_url, skill_name, custom_path, commit.as_deref())
         .context("Failed to download skill")?;
 
+    // Reject skills without a valid frontmatter before touching anything
+    if let Err(e) = SkillManifest::parse(&fetched.content) {
+        spin.stop("Invalid skill.");
+        return Err(e.context(format!("{} is not a valid skill", fetched.path)));
+    }
+
+    // Pin the exact revision so `install` reproduces this content
+    let (files, locked) = match pin_skill(&client, repo_url, fetched) {
+        Ok(pinned) => pinned,
+        Err(e) => {
+            spin.stop("Could not pin.");
+            return Err(e);
+        }
+    };
+
     spin.stop("Downloaded.");
 
     // Check if skill already exists and verify hash
-    if let Some(existing) = config.skills.get(skill_name) {
-        let new_hash = SkillStore::calculate_hash(&content);
+    if let Some(existing) = config.skills.get(installed_name) {
+        let new_hash = &locked.hash;
         
-        if new_hash != existing.hash {
+        if *new_hash != existing.hash {
             log::warning("Skill exists with different content.")?;
-            let should_update = confirm("Do you want to overwrite local skill with remote version?").interact()?;
+            let should_update = ui.confirm("Do you want to overwrite local skill with remote version?")?;
             if !should_update {
                 outro("Update cancelled.")?;
                 return Ok(());
@@ -210,31 +315,428 @@ fn cmd_add(repo_url: &str, skill_name: &str, custom_path: Option<String>) -> Res
         }
     }
 
+    install_locked_skill(&mut config, installed_name, skill_name, repo_url, reference, &files, locked)?;
+
+    match (reference, &commit) {
+        (Some(reference), Some(sha)) => log::success(format!("Installed at {} ({}).", reference, short_ref(sha)))?,
+        _ => log::success("Installed.")?,
+    }
+    outro(format!("{} is now active for {:?}", installed_name, config.active_editors))?;
+
+    Ok(())
+}
+
+/// Write a downloaded skill to the store under `skill_name`, record it in skills.json
+/// and skills.lock, and link it into every active editor. `upstream_name` is the
+/// skill's name in its repository, which differs from `skill_name` for aliases, and
+/// `reference` the branch, tag or commit the skill tracks (the default branch if none).
+fn install_locked_skill(config: &mut SkillConfig, skill_name: &str, upstream_name: &str, repo_url: &str, reference: Option<&str>, files: &[SkillFile], locked: LockedSkill) -> Result<()> {
     // Install to store
-    let store = SkillStore::new(&config.store_path)?;
-    let entry = store.install_skill(skill_name, &content, repo_url)?;
+    let store = open_store(config)?;
+    let mut entry = store.install_skill_files(skill_name, files, repo_url)?;
+    entry.path = Some(locked.path.clone());
+    entry.resolved_ref = Some(locked.resolved_ref.clone());
+    entry.skill = (upstream_name != skill_name).then(|| upstream_name.to_string());
+    entry.reference = reference.map(str::to_string);
     
     // Update config
-    update_skill_in_config(&mut config, skill_name, entry.clone())?;
+    update_skill_in_config(config, skill_name, entry)?;
+
+    let mut lockfile = load_lockfile(&config.scope)?;
+    lockfile.skills.insert(skill_name.to_string(), locked);
+    save_lockfile(&config.scope, &lockfile)?;
+
+    link_into_editors(config, &store, skill_name)
+}
 
-    // Inject references for all active editors
+/// Inject references to an installed skill into every active editor
+fn link_into_editors(config: &SkillConfig, store: &SkillStore, skill_name: &str) -> Result<()> {
     let skill_path = store.get_skill_path(skill_name)?;
-    for editor in &config.active_editors {
-        inject_reference(editor, skill_name, &skill_path)?;
+    for adapter in config.active_adapters()? {
+        let adapter = adapter.as_ref();
+        inject_reference(adapter, skill_name, &skill_path, config.rule_overrides.get(skill_name), config.delivery_for(adapter))?;
+    }
+
+    Ok(())
+}
+
+/// Resolve the branch a skill was found on to a commit and download its whole
+/// directory from there. Fails rather than locking a moving branch: skills.lock
+/// must always name the exact revision.
+fn pin_skill(client: &SecureHttpClient, repo_url: &str, fetched: FetchedSkill) -> Result<(Vec<SkillFile>, LockedSkill)> {
+    let resolved_ref = client.resolve_commit(repo_url, &fetched.branch)
+        .with_context(|| format!("Could not pin '{}' to a commit", fetched.branch))?;
+    let raw_url = client.raw_file_url(repo_url, &resolved_ref, &fetched.path)?;
+    let files = client.fetch_skill_dir(repo_url, &resolved_ref, &fetched.path)
+        .context("Failed to download pinned revision")?;
+
+    let file_hashes = SkillStore::file_hashes(&files);
+    let locked = LockedSkill {
+        source: repo_url.to_string(),
+        resolved_ref,
+        path: fetched.path,
+        raw_url,
+        hash: SkillStore::calculate_tree_hash(&file_hashes),
+        files: if file_hashes.len() > 1 { file_hashes } else { Default::default() },
+    };
+
+    Ok((files, locked))
+}
+
+/// Add a skill from a local directory: `source` is the skill itself or a checkout
+/// holding `skill_name`. The files are copied into the store, or linked with `dev`.
+fn cmd_add_local(ui: &Interaction, scope: &Scope, source: &Path, skill_name: Option<&str>, custom_path: Option<&str>, alias: Option<&str>, dev: bool) -> Result<()> {
+    let mut config = load_config(scope)
+        .context("Please run 'skillctl init' first.")?;
+
+    let skill_dir = local::find_local_skill(source, skill_name, custom_path)?;
+    let files = local::read_local_skill(&skill_dir)?;
+
+    // Reject skills without a valid frontmatter before touching anything
+    let content = files.iter()
+        .find(|f| f.path == "SKILL.md")
+        .map(|f| f.content.as_str())
+        .unwrap_or_default();
+    let manifest = SkillManifest::parse(content)
+        .with_context(|| format!("{} is not a valid skill", skill_dir.join("SKILL.md").display()))?;
+
+    // The name defaults to the frontmatter's, then to the directory's
+    let dir_name = skill_dir.file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let upstream_name = skill_name.map(str::to_string)
+        .or(manifest.name)
+        .unwrap_or(dir_name);
+    validate_skill_name(&upstream_name)
+        .context("Invalid skill name (use --as <name> to choose another)")?;
+    let installed_name = alias.unwrap_or(&upstream_name);
+    validate_skill_name(installed_name)
+        .context("Invalid alias")?;
+
+    // A name belongs to one source; another directory's skill needs an alias
+    config.check_name_conflict(installed_name, &local::source_url(&skill_dir)?, &upstream_name)?;
+
+    if let Some(existing) = config.skills.get(installed_name) {
+        if !dev && SkillStore::hash_files(&files) != existing.hash {
+            log::warning("Skill exists with different content.")?;
+            if !ui.confirm("Do you want to overwrite the installed skill with the local version?")? {
+                outro("Update cancelled.")?;
+                return Ok(());
+            }
+        }
     }
 
-    log::success("Installed.")?;
-    outro(format!("{} is now active for {:?}", skill_name, config.active_editors))?;
+    install_local_skill(&mut config, installed_name, &upstream_name, &skill_dir, &files, dev)?;
+
+    if dev {
+        log::success(format!("Linked to {}.", skill_dir.display()))?;
+    } else {
+        log::success(format!("Copied from {}.", skill_dir.display()))?;
+    }
+    outro(format!("{} is now active for {:?}", installed_name, config.active_editors))?;
 
     Ok(())
 }
 
+/// Copy a local skill directory into the store under `skill_name` (link it with
+/// `dev`), record it in skills.json and link it into every active editor. Local
+/// skills have no revision to pin, so they are left out of skills.lock.
+fn install_local_skill(config: &mut SkillConfig, skill_name: &str, upstream_name: &str, skill_dir: &Path, files: &[SkillFile], dev: bool) -> Result<()> {
+    let source = local::source_url(skill_dir)?;
+    let store = open_store(config)?.with_linked_sources([skill_dir.to_path_buf()]);
+    let mut entry = if dev {
+        store.link_local_skill(skill_name, skill_dir, &source)?
+    } else {
+        store.install_skill_files(skill_name, files, &source)?
+    };
+    entry.skill = (upstream_name != skill_name).then(|| upstream_name.to_string());
+
+    update_skill_in_config(config, skill_name, entry)?;
+
+    let mut lockfile = load_lockfile(&config.scope)?;
+    if lockfile.skills.remove(skill_name).is_some() {
+        save_lockfile(&config.scope, &lockfile)?;
+    }
+
+    link_into_editors(config, &store, skill_name)
+}
+
+// ============================================================================
+// COMMAND: UPDATE
+// ============================================================================
+
+fn cmd_update(ui: &Interaction, scope: &Scope, skill_names: &[String]) -> Result<()> {
+    let mut config = load_config(scope)
+        .context("Configuration not found. Please run 'skillctl init' first.")?;
+
+    if config.skills.is_empty() {
+        log::warning("No skills installed.")?;
+        return Ok(());
+    }
+
+    let targets = select_installed_skills(&config, skill_names)?;
+
+    let store = open_store(&config)?;
+    let client = http_client(&config)?;
+    let mut updated = Vec::new();
+    let mut skipped = Vec::new();
+
+    for (name, entry) in targets {
+        let spin = spinner();
+        spin.start(format!("Checking {}...", name));
+
+        if entry.dev {
+            spin.stop(format!("{} is linked to its local directory.", name));
+            continue;
+        }
+
+        let (files, locked) = match fetch_upstream(&client, &name, &entry) {
+            Ok(upstream) => upstream,
+            Err(e) => {
+                spin.stop("Failed.");
+                log::error(format!("Could not check {}: {}", name, e))?;
+                continue;
+            }
+        };
+
+        if SkillStore::hash_files(&files) == entry.hash {
+            spin.stop(format!("{} is up to date.", name));
+            continue;
+        }
+
+        spin.stop(format!("{} has upstream changes ({}).", name, upstream_revision(locked.as_ref())));
+
+        let current = store.read_skill_files(&name).unwrap_or_default();
+        print_skill_diff(&current, &files);
+
+        let accept = ui.confirm(&format!("Update {}?", name))?;
+        if !accept {
+            skipped.push(name);
+            continue;
+        }
+
+        match (locked, local::source_dir(&entry.url)) {
+            (Some(locked), _) => install_locked_skill(&mut config, &name, entry.upstream_name(&name), &entry.url, entry.reference.as_deref(), &files, locked)?,
+            (None, Some(dir)) => install_local_skill(&mut config, &name, entry.upstream_name(&name), &dir, &files, false)?,
+            (None, None) => unreachable!("remote skills are always locked"),
+        }
+        log::success(format!("✓ Updated {}", style(&name).green()))?;
+        updated.push(name);
+    }
+
+    if !skipped.is_empty() {
+        log::info(format!("Skipped: {}", skipped.join(", ")))?;
+    }
+
+    outro(format!("Updated {} skill(s)", updated.len()))?;
+    Ok(())
+}
+
+/// Pick the requested installed skills (all of them when none are named)
+fn select_installed_skills(config: &SkillConfig, skill_names: &[String]) -> Result<Vec<(String, SkillEntry)>> {
+    let mut selected: Vec<(String, SkillEntry)> = if skill_names.is_empty() {
+        config.skills.iter()
+            .map(|(name, entry)| (name.clone(), entry.clone()))
+            .collect()
+    } else {
+        let mut selected = Vec::new();
+        let mut not_found = Vec::new();
+        for requested in skill_names {
+            match config.resolve_skill_name(requested)? {
+                Some(name) => {
+                    let entry = config.skills[&name].clone();
+                    selected.push((name, entry));
+                },
+                None => not_found.push(requested.clone()),
+            }
+        }
+        if !not_found.is_empty() {
+            log::warning(format!("Skills not found: {}", not_found.join(", ")))?;
+        }
+        selected
+    };
+
+    selected.sort_by(|a, b| a.0.cmp(&b.0));
+    Ok(selected)
+}
+
+/// Fetch the latest upstream revision of an installed skill: the current commit of
+/// the branch or tag it was added with, or of the default branch. Local skills
+/// are read from their directory and have no lock.
+fn fetch_upstream(client: &SecureHttpClient, name: &str, entry: &SkillEntry) -> Result<(Vec<SkillFile>, Option<LockedSkill>)> {
+    if let Some(dir) = local::source_dir(&entry.url) {
+        return Ok((local::read_local_skill(&dir)?, None));
+    }
+
+    let commit = entry.reference.as_deref()
+        .map(|reference| client.resolve_commit(&entry.url, reference))
+        .transpose()?;
+    let fetched = client.find_skill(&entry.url, entry.upstream_name(name), entry.path.clone(), commit.as_deref())?;
+    let (files, locked) = pin_skill(client, &entry.url, fetched)?;
+    Ok((files, Some(locked)))
+}
+
+/// The revision an upstream version was read at, for display
+fn upstream_revision(locked: Option<&LockedSkill>) -> &str {
+    locked.map_or("local", |locked| short_ref(&locked.resolved_ref))
+}
+
+/// Print the diff of every file that changed between two versions of a skill
+fn print_skill_diff(old: &[SkillFile], new: &[SkillFile]) {
+    let mut paths: Vec<&str> = old.iter().chain(new).map(|f| f.path.as_str()).collect();
+    paths.sort();
+    paths.dedup();
+
+    let find = |files: &[SkillFile], path: &str| files.iter()
+        .find(|f| f.path == path)
+        .map(|f| f.content.clone())
+        .unwrap_or_default();
+
+    for path in paths {
+        let (old_content, new_content) = (find(old, path), find(new, path));
+        if old_content != new_content {
+            print_diff(&old_content, &new_content, path);
+        }
+    }
+}
+
+/// Print a colored unified diff between two versions of a file
+fn print_diff(old: &str, new: &str, path: &str) {
+    let diff = similar::TextDiff::from_lines(old, new);
+    let unified = diff.unified_diff()
+        .context_radius(3)
+        .header(&format!("a/{}", path), &format!("b/{}", path))
+        .to_string();
+
+    for line in unified.lines() {
+        if line.starts_with("+++") || line.starts_with("---") {
+            println!("{}", style(line).bold());
+        } else if line.starts_with('+') {
+            println!("{}", style(line).green());
+        } else if line.starts_with('-') {
+            println!("{}", style(line).red());
+        } else if line.starts_with("@@") {
+            println!("{}", style(line).cyan());
+        } else {
+            println!("{}", line);
+        }
+    }
+    println!();
+}
+
+// ============================================================================
+// COMMAND: OUTDATED
+// ============================================================================
+
+fn cmd_outdated(scope: &Scope, skill_names: &[String]) -> Result<()> {
+    let config = load_config(scope)
+        .context("Configuration not found. Please run 'skillctl init' first.")?;
+
+    if config.skills.is_empty() {
+        log::warning("No skills installed.")?;
+        return Ok(());
+    }
+
+    let targets = select_installed_skills(&config, skill_names)?;
+
+    let client = http_client(&config)?;
+    let mut outdated = Vec::new();
+    let mut errors = Vec::new();
+
+    let spin = spinner();
+    spin.start(format!("Checking {} skill(s)...", targets.len()));
+
+    let mut rows = Vec::new();
+    for (name, entry) in &targets {
+        // A --dev skill always is its local directory
+        if entry.dev {
+            rows.push((name, entry, Ok((entry.hash.clone(), "local".to_string()))));
+            continue;
+        }
+        match fetch_upstream(&client, name, entry) {
+            Ok((files, locked)) => {
+                let revision = upstream_revision(locked.as_ref()).to_string();
+                rows.push((name, entry, Ok((SkillStore::hash_files(&files), revision))));
+            },
+            Err(e) => rows.push((name, entry, Err(e))),
+        }
+    }
+
+    spin.stop("Check complete.");
+
+    for (name, entry, upstream) in rows {
+        let age = format_age(&entry.last_updated);
+
+        match upstream {
+            Ok((hash, revision)) if hash == entry.hash => {
+                println!("   {} {}  {}  {}",
+                    style("●").green(),
+                    style(name).bold(),
+                    style(short_hash(&entry.hash)).dim(),
+                    style(format!("up to date @ {} [{}]", revision, age)).dim()
+                );
+            },
+            Ok((hash, revision)) => {
+                println!("   {} {}  {} → {}  {}",
+                    style("●").yellow(),
+                    style(name).bold(),
+                    style(short_hash(&entry.hash)).red(),
+                    style(short_hash(&hash)).green(),
+                    style(format!("@ {} [{}]", revision, age)).dim()
+                );
+                outdated.push(name.clone());
+            },
+            Err(e) => {
+                println!("   {} {}  {}  {}",
+                    style("●").red(),
+                    style(name).bold(),
+                    style(short_hash(&entry.hash)).dim(),
+                    style(format!("check failed: {}", e)).red()
+                );
+                errors.push(name.clone());
+            }
+        }
+    }
+    println!();
+
+    if !errors.is_empty() {
+        anyhow::bail!("Could not check {} skill(s): {}", errors.len(), errors.join(", "));
+    }
+    if !outdated.is_empty() {
+        anyhow::bail!("{} skill(s) outdated: {}. Run 'skillctl update' to refresh them.", outdated.len(), outdated.join(", "));
+    }
+
+    outro("All skills are up to date.")?;
+    Ok(())
+}
+
+/// Shorten a SHA256 hash for display
+fn short_hash(hash: &str) -> &str {
+    &hash[..hash.len().min(12)]
+}
+
+/// Human-readable time elapsed since an RFC 3339 timestamp
+fn format_age(timestamp: &str) -> String {
+    let Ok(then) = chrono::DateTime::parse_from_rfc3339(timestamp) else {
+        return "??".to_string();
+    };
+
+    let elapsed = chrono::Utc::now().signed_duration_since(then);
+    if elapsed.num_days() > 0 {
+        format!("{}d ago", elapsed.num_days())
+    } else if elapsed.num_hours() > 0 {
+        format!("{}h ago", elapsed.num_hours())
+    } else {
+        format!("{}m ago", elapsed.num_minutes().max(0))
+    }
+}
+
 // ============================================================================
 // COMMAND: REMOVE
 // ============================================================================
 
-fn cmd_remove(skill_names: &[String]) -> Result<()> {
-    let mut config = load_config()
+fn cmd_remove(scope: &Scope, skill_names: &[String]) -> Result<()> {
+    let mut config = load_config(scope)
         .context("Configuration not found. Please run 'skillctl init' first.")?;
     
     if config.skills.is_empty() {
@@ -244,11 +746,20 @@ fn cmd_remove(skill_names: &[String]) -> Result<()> {
 
     log::info(format!("Removing {} skill(s)...", skill_names.len()))?;
     
-    let store = SkillStore::new(&config.store_path)?;
+    let store = open_store(&config)?;
+    let adapters = config.active_adapters()?;
+    let mut lockfile = load_lockfile(&config.scope)?;
     let mut removed_count = 0;
     let mut not_found = Vec::new();
 
-    for skill_name in skill_names {
+    for requested in skill_names {
+        // Accept qualified ids (owner/repo:skill) as well as installed names
+        let Some(skill_name) = config.resolve_skill_name(requested)? else {
+            not_found.push(requested.clone());
+            continue;
+        };
+        let skill_name = skill_name.as_str();
+
         // Validate skill name (security)
         if let Err(e) = validate_skill_name(skill_name) {
             log::warning(format!("Invalid skill name '{}': {}", skill_name, e))?;
@@ -258,20 +769,22 @@ fn cmd_remove(skill_names: &[String]) -> Result<()> {
         if remove_skill_from_config(&mut config, skill_name)?.is_some() {
             // Remove from filesystem
             store.remove_skill(skill_name)?;
+            lockfile.skills.remove(skill_name);
 
             // Remove references from all active editors
-            for editor in &config.active_editors {
-                remove_reference(editor, skill_name)?;
+            for adapter in &adapters {
+                remove_reference(adapter.as_ref(), skill_name)?;
             }
 
             removed_count += 1;
             log::info(format!("✓ Removed {}", style(skill_name).green()))?;
         } else {
-            not_found.push(skill_name.clone());
+            not_found.push(requested.clone());
         }
     }
 
     if removed_count > 0 {
+        save_lockfile(&config.scope, &lockfile)?;
         outro(format!("Removed {} skill(s)", removed_count))?;
     }
     
@@ -286,50 +799,105 @@ fn cmd_remove(skill_names: &[String]) -> Result<()> {
 // COMMAND: LIST
 // ============================================================================
 
-fn cmd_list() -> Result<()> {
-    let config = load_config()
+fn cmd_list(scope: &Scope, format: OutputFormat) -> Result<()> {
+    let config = load_config(scope)
         .context("Configuration not found. Please run 'skillctl init' first.")?;
+    let global = config.load_inherited()?;
+
+    // Project skills first, then the global skills they do not override
+    let mut skills: Vec<(&String, &SkillEntry, Scope)> = config.skills.iter()
+        .map(|(name, entry)| (name, entry, config.scope.clone()))
+        .collect();
+    skills.sort_by(|a, b| a.0.cmp(b.0));
+    if let Some(global) = &global {
+        skills.extend(config.inherited_skills(global).into_iter()
+            .map(|(name, entry)| (name, entry, global.scope.clone())));
+    }
+
+    if format == OutputFormat::Json {
+        #[derive(serde::Serialize)]
+        struct SkillListing<'a> {
+            name: &'a str,
+            id: String,
+            scope: String,
+            #[serde(flatten)]
+            entry: &'a SkillEntry,
+        }
+
+        let listings: Vec<SkillListing> = skills.iter()
+            .map(|(name, entry, scope)| SkillListing { name, id: entry.qualified_id(name), scope: scope.to_string(), entry })
+            .collect();
+        return print_json(&listings);
+    }
     
-    if config.skills.is_empty() {
+    if skills.is_empty() {
         log::warning("No skills installed.")?;
         outro_note(style("Hint").cyan(), "Try running 'skillctl search' to find skills.")?;
         return Ok(());
     }
 
-    log::info(format!("{} installed skills:", style(config.skills.len()).cyan()))?;
+    log::info(format!("{} installed skills:", style(skills.len()).cyan()))?;
 
-    for (name, entry) in config.skills {
+    for (name, entry, skill_scope) in skills {
         let date = chrono::DateTime::parse_from_rfc3339(&entry.last_updated)
             .map(|dt| dt.format("%Y-%m-%d").to_string())
             .unwrap_or_else(|_| "??".to_string());
+        let origin = if skill_scope != config.scope { " (global)" } else { "" };
+        let source = match (&entry.reference, entry.dev) {
+            (Some(reference), _) => format!("{}@{}", entry.qualified_id(name), reference),
+            (None, true) => format!("{} (linked)", entry.qualified_id(name)),
+            (None, false) => entry.qualified_id(name),
+        };
             
-        println!("   {} {}  {}", 
+        println!("   {} {}{}  {}  {}", 
             style("●").green(), 
-            style(&name).bold(), 
+            style(name).bold(), 
+            style(origin).cyan(),
+            style(source).dim(),
             style(format!("[{}]", date)).dim()
         );
+
+        if let Some(manifest) = read_manifest(entry) {
+            println!("     {}", style(manifest.description).dim());
+        }
     }
     println!();
     Ok(())
 }
 
+/// Parse the frontmatter of an installed skill, if it has a valid one
+fn read_manifest(entry: &SkillEntry) -> Option<SkillManifest> {
+    let content = fs::read_to_string(&entry.local_path).ok()?;
+    SkillManifest::parse(&content).ok()
+}
+
 // ============================================================================
 // COMMAND: INSTALL (Restore from skills.json)
 // ============================================================================
 
-fn cmd_install() -> Result<()> {
-    let config = load_config()
+fn cmd_install(scope: &Scope) -> Result<()> {
+    let config = load_config(scope)
         .context("Configuration not found. Please run 'skillctl init' first.")?;
     
-    if config.skills.is_empty() {
+    let adapters = config.active_adapters()?;
+    let inheritance = load_inheritance(&config, &adapters)?;
+
+    if config.skills.is_empty() && inheritance.is_none() {
         log::warning("No skills in configuration.")?;
         return Ok(());
     }
 
     log::info(format!("Restoring {} skill(s)...", config.skills.len()))?;
 
-    let store = SkillStore::new(&config.store_path)?;
-    let client = SecureHttpClient::new()?;
+    let store = open_store(&config)?;
+    let client = http_client(&config)?;
+    let lockfile = load_lockfile(&config.scope)?;
+
+    let mut verified = Vec::new();
+    let mut restored = Vec::new();
+    let mut failed = Vec::new();
+    let mut tampered = Vec::new();
+    let mut refreshed = Vec::new();
 
     for (name, entry) in &config.skills {
         // Validate skill name (security)
@@ -338,63 +906,392 @@ fn cmd_install() -> Result<()> {
             continue;
         }
 
+        let locked = lockfile.skills.get(name);
+        if let Some(locked) = locked {
+            if locked.hash != entry.hash {
+                log::error(format!("{}: skills.lock and skills.json disagree on the content hash", name))?;
+                tampered.push(name.clone());
+                continue;
+            }
+        }
+
         let local_path = store.get_skill_path(name)?;
 
         // Check if file exists and verify integrity
-        if !store.verify_skill(name, &entry.hash)? {
+        if entry.dev {
+            match ensure_dev_link(&store, name, entry) {
+                Ok(false) => verified.push(name.clone()),
+                Ok(true) => {
+                    log::info(format!("{}: relinked to its local directory", name))?;
+                    restored.push(name.clone());
+                },
+                Err(e) => {
+                    log::error(format!("Could not link {}: {}", name, e))?;
+                    failed.push(name.clone());
+                    continue;
+                }
+            }
+        } else if store.verify_skill(name, &entry.hash)? {
+            store.share_skill(name)?;
+            verified.push(name.clone());
+        } else {
             let spin = spinner();
             spin.start(format!("Restoring {}...", name));
 
-            // Re-download
-            match client.download(&entry.url) {
-                Ok(content) => {
-                    fs::create_dir_all(local_path.parent().unwrap())?;
-                    fs::write(&local_path, content)?;
-                    spin.stop("Restored.");
+            match restore_skill(&client, name, entry, locked) {
+                Ok(files) if SkillStore::hash_files(&files) == entry.hash => {
+                    store.install_skill_files(name, &files, &entry.url)?;
+                    match locked.map(|l| l.resolved_ref.as_str()).or(entry.resolved_ref.as_deref()) {
+                        Some(reference) => spin.stop(format!("Restored at {}.", short_ref(reference))),
+                        None => spin.stop("Restored."),
+                    }
+                    restored.push(name.clone());
+                },
+                Ok(_) => {
+                    spin.stop("Tampered.");
+                    log::error(format!("{}: upstream content does not match the recorded hash", name))?;
+                    tampered.push(name.clone());
+                    continue;
                 },
                 Err(e) => {
                     spin.stop("Failed.");
                     log::error(format!("Could not restore {}: {}", name, e))?;
+                    failed.push(name.clone());
+                    continue;
                 }
             }
         }
 
-        // Always check references for all active editors
-        for editor in &config.active_editors {
-            inject_reference(editor, name, &local_path)?;
+        // Always check references for all active editors, refreshing stale copies
+        for adapter in &adapters {
+            let adapter = adapter.as_ref();
+            let delivery = config.delivery_for(adapter);
+            if is_delivery_stale(adapter, name, &local_path, delivery)? {
+                log::info(format!("{}: refreshed stale {} copy", name, adapter.name()))?;
+                refreshed.push(format!("{} ({})", name, adapter.name()));
+            }
+            inject_reference(adapter, name, &local_path, config.rule_overrides.get(name), delivery)?;
+        }
+    }
+
+    // Global skills the project does not override
+    if let Some(inheritance) = &inheritance {
+        for adapter in adapters.iter().filter(|a| inheritance.adapters.iter().any(|n| n == a.name())) {
+            let adapter = adapter.as_ref();
+            for (name, skill_path) in &inheritance.skills {
+                inject_reference(adapter, name, skill_path, config.rule_overrides.get(name), config.delivery_for(adapter))?;
+            }
+        }
+        if !inheritance.skills.is_empty() {
+            log::info(format!("{} global skill(s) inherited", style(inheritance.skills.len()).green()))?;
         }
     }
 
+    log::info(format!("{} verified, {} restored, {} refreshed, {} failed, {} tampered",
+        style(verified.len()).green(),
+        style(restored.len()).green(),
+        style(refreshed.len()).green(),
+        style(failed.len()).red(),
+        style(tampered.len()).red()
+    ))?;
+
+    if !failed.is_empty() {
+        log::error(format!("Failed: {}", failed.join(", ")))?;
+    }
+    if !tampered.is_empty() {
+        log::error(format!("Hash mismatch: {}", tampered.join(", ")))?;
+    }
+    if !failed.is_empty() || !tampered.is_empty() {
+        anyhow::bail!("{} skill(s) could not be restored", failed.len() + tampered.len());
+    }
+
     outro("All skills verified and linked.")?;
     Ok(())
 }
 
+/// Global skills a project inherits and the project editors that need them
+struct Inheritance {
+    /// Name and global store SKILL.md of every intact inherited skill
+    skills: Vec<(String, PathBuf)>,
+    /// Project adapters whose editor the global config does not serve at user level
+    adapters: Vec<String>,
+}
+
+/// Resolve the global skills inherited by a project (`None` when it inherits nothing)
+fn load_inheritance(config: &SkillConfig, adapters: &[Box<dyn EditorAdapter>]) -> Result<Option<Inheritance>> {
+    let Some(global) = config.load_inherited()? else {
+        return Ok(None);
+    };
+
+    let global_store = open_store(&global)?;
+    let mut skills = Vec::new();
+    for (name, entry) in config.inherited_skills(&global) {
+        if validate_skill_name(name).is_err() || !global_store.verify_skill(name, &entry.hash)? {
+            log::warning(format!("{}: global skill is missing or modified. Run 'skillctl --global install'.", name))?;
+            continue;
+        }
+        skills.push((name.clone(), global_store.get_skill_path(name)?));
+    }
+
+    let adapters = SkillConfig::inheriting_adapters(adapters, &global)?
+        .iter()
+        .map(|a| a.name().to_string())
+        .collect();
+
+    Ok(Some(Inheritance { skills, adapters }))
+}
+
+/// Download the files recorded for an installed skill.
+/// Uses the pinned revision from skills.lock when available, otherwise re-runs
+/// `find_skill` with the path and ref recorded in skills.json. Local skills are
+/// read again from their directory.
+fn restore_skill(client: &SecureHttpClient, name: &str, entry: &SkillEntry, locked: Option<&LockedSkill>) -> Result<Vec<SkillFile>> {
+    if let Some(dir) = local::source_dir(&entry.url) {
+        return local::read_local_skill(&dir);
+    }
+
+    if let Some(locked) = locked {
+        if locked.files.is_empty() {
+            let content = client.download(&locked.raw_url)?;
+            return Ok(vec![SkillFile::new("SKILL.md", content)]);
+        }
+        return client.fetch_skill_dir(&locked.source, &locked.resolved_ref, &locked.path);
+    }
+
+    let fetched = client.find_skill(&entry.url, entry.upstream_name(name), entry.path.clone(), entry.resolved_ref.as_deref())?;
+    match (&entry.resolved_ref, entry.files.is_empty()) {
+        (Some(reference), false) => client.fetch_skill_dir(&entry.url, reference, &fetched.path),
+        _ => Ok(vec![SkillFile::new("SKILL.md", fetched.content)]),
+    }
+}
+
+/// Check that a `--dev` skill links to its local directory, relinking it when the
+/// link is missing or broken. Returns whether it was relinked.
+fn ensure_dev_link(store: &SkillStore, name: &str, entry: &SkillEntry) -> Result<bool> {
+    let dir = local::source_dir(&entry.url)
+        .with_context(|| format!("{} is a --dev skill without a local source", entry.url))?;
+    if store.verify_link(name, &dir)? {
+        return Ok(false);
+    }
+    store.link_local_skill(name, &dir, &entry.url)?;
+    Ok(true)
+}
+
+/// Abbreviate a commit SHA for display, leaving branch names untouched
+fn short_ref(reference: &str) -> &str {
+    if reference.len() == 40 && reference.chars().all(|c| c.is_ascii_hexdigit()) {
+        &reference[..7]
+    } else {
+        reference
+    }
+}
+
+// ============================================================================
+// COMMAND: VERIFY
+// ============================================================================
+
+fn cmd_verify(scope: &Scope, fix: bool) -> Result<()> {
+    let config = load_config(scope)
+        .context("Configuration not found. Please run 'skillctl init' first.")?;
+
+    let store = open_store(&config)?;
+    let lockfile = load_lockfile(&config.scope)?;
+    let mut client = None;
+    let mut problems = 0;
+    let mut fixed = 0;
+
+    let adapters = config.active_adapters()?;
+    log::info(format!("Verifying {} skill(s) across {} editor(s)...", config.skills.len(), adapters.len()))?;
+
+    let mut names: Vec<&String> = config.skills.keys().collect();
+    names.sort();
+
+    // 1. Store content matches skills.json
+    for name in &names {
+        let entry = &config.skills[*name];
+        if validate_skill_name(name).is_err() {
+            log::error(format!("{}: invalid skill name in skills.json", name))?;
+            problems += 1;
+            continue;
+        }
+        if entry.dev {
+            let linked = match local::source_dir(&entry.url) {
+                Some(dir) => store.verify_link(name, &dir)?,
+                None => false,
+            };
+            if linked {
+                continue;
+            }
+
+            problems += 1;
+            log::error(format!("{}: link to its local directory is missing or broken", name))?;
+
+            if fix {
+                match ensure_dev_link(&store, name, entry) {
+                    Ok(_) => {
+                        log::success(format!("  relinked {}", name))?;
+                        fixed += 1;
+                    },
+                    Err(e) => log::error(format!("  could not relink {}: {}", name, e))?,
+                }
+            }
+            continue;
+        }
+        if store.verify_skill(name, &entry.hash)? {
+            continue;
+        }
+
+        problems += 1;
+        log::error(format!("{}: store content is missing or does not match its hash", name))?;
+
+        if fix {
+            let client = match &client {
+                Some(client) => client,
+                None => client.insert(http_client(&config)?),
+            };
+            match restore_skill(client, name, entry, lockfile.skills.get(*name)) {
+                Ok(files) if SkillStore::hash_files(&files) == entry.hash => {
+                    store.install_skill_files(name, &files, &entry.url)?;
+                    log::success(format!("  restored {}", name))?;
+                    fixed += 1;
+                },
+                Ok(_) => log::error(format!("  upstream content for {} does not match the recorded hash", name))?,
+                Err(e) => log::error(format!("  could not restore {}: {}", name, e))?,
+            }
+        }
+    }
+
+    // 2. Orphan store directories
+    for dir in store.list_skills()? {
+        if config.skills.contains_key(&dir) {
+            continue;
+        }
+
+        problems += 1;
+        log::warning(format!("{}: orphan directory in store", dir))?;
+
+        if fix && validate_skill_name(&dir).is_ok() {
+            store.remove_skill(&dir)?;
+            log::success(format!("  removed {}", dir))?;
+            fixed += 1;
+        }
+    }
+
+    // 3. Editor references: missing and orphaned
+    let inheritance = load_inheritance(&config, &adapters)?;
+    for adapter in &adapters {
+        let adapter = adapter.as_ref();
+        let referenced = list_references(adapter)?;
+
+        // Project skills, plus inherited global skills for editors that need them
+        let mut expected = Vec::new();
+        for name in names.iter().filter(|n| validate_skill_name(n).is_ok()) {
+            expected.push(((*name).clone(), store.get_skill_path(name)?));
+        }
+        if let Some(inheritance) = inheritance.as_ref().filter(|i| i.adapters.iter().any(|n| n == adapter.name())) {
+            expected.extend(inheritance.skills.iter().cloned());
+        }
+
+        for (name, skill_path) in &expected {
+            if referenced.contains(name) {
+                continue;
+            }
+
+            problems += 1;
+            log::warning(format!("{}: missing reference for {}", adapter.name(), name))?;
+
+            if fix {
+                inject_reference(adapter, name, skill_path, config.rule_overrides.get(name), config.delivery_for(adapter))?;
+                log::success(format!("  linked {}", name))?;
+                fixed += 1;
+            }
+        }
+
+        for name in referenced.iter().filter(|n| !expected.iter().any(|(e, _)| e == *n)) {
+            problems += 1;
+            log::warning(format!("{}: orphan reference to {}", adapter.name(), name))?;
+
+            if fix {
+                remove_reference(adapter, name)?;
+                log::success(format!("  unlinked {}", name))?;
+                fixed += 1;
+            }
+        }
+    }
+
+    if problems == 0 {
+        outro("Everything is consistent.")?;
+        return Ok(());
+    }
+
+    if fix && fixed == problems {
+        outro(format!("Fixed {} problem(s).", fixed))?;
+        return Ok(());
+    }
+
+    if !fix {
+        log::info("Run 'skillctl verify --fix' to repair.")?;
+    }
+    anyhow::bail!("{} problem(s) found, {} fixed", problems, fixed)
+}
+
 // ============================================================================
 // COMMAND: SEARCH
 // ============================================================================
 
-fn cmd_search() -> Result<()> {
-    let spin = spinner();
-    spin.start("Fetching registry...");
-    
+fn cmd_search(ui: &Interaction, scope: &Scope, format: OutputFormat) -> Result<()> {
     let registry_url = "https://raw.githubusercontent.com/joeldevz/agent-skill/refs/heads/main/registry.json";
-    
     let client = SecureHttpClient::new()?;
-    let content = client.download(registry_url)?;
-    
-    spin.stop("Registry loaded.");
 
-    #[derive(serde::Deserialize)]
+    #[derive(serde::Deserialize, serde::Serialize)]
     struct RegistryItem {
         name: String,
+        #[serde(default)]
         description: String,
         url: String,
         #[serde(default)]
         skill_path: Option<String>,
+        /// Set locally when the skill is already installed in this project
+        #[serde(default)]
+        installed: bool,
+    }
+
+    // Enrich registry entries with what the installed SKILL.md declares
+    let enrich = |items: &mut Vec<RegistryItem>| {
+        let installed = load_config(scope).map(|c| c.skills).unwrap_or_default();
+        for item in items {
+            let skill_id = item.skill_path.as_deref().unwrap_or(&item.name);
+            if let Some(entry) = installed.get(skill_id) {
+                item.installed = true;
+                if item.description.is_empty() {
+                    if let Some(manifest) = read_manifest(entry) {
+                        item.description = manifest.description;
+                    }
+                }
+            }
+        }
+    };
+
+    if format == OutputFormat::Json {
+        let content = client.download(registry_url)?;
+        let mut items: Vec<RegistryItem> = serde_json::from_str(&content)
+            .context("Failed to parse registry")?;
+        enrich(&mut items);
+        return print_json(&items);
     }
 
-    let items: Vec<RegistryItem> = serde_json::from_str(&content)
+    ui.require_tty("Search", "use 'skillctl add <url> --skill <name>' or --format json instead")?;
+
+    let spin = spinner();
+    spin.start("Fetching registry...");
+    
+    let content = client.download(registry_url)?;
+    
+    spin.stop("Registry loaded.");
+
+    let mut items: Vec<RegistryItem> = serde_json::from_str(&content)
         .unwrap_or_default();
+    enrich(&mut items);
 
     if items.is_empty() {
         log::warning("Registry is empty.")?;
@@ -403,7 +1300,11 @@ fn cmd_search() -> Result<()> {
 
     // Fuzzy Search
     let options: Vec<String> = items.iter()
-        .map(|i| format!("{} - {}", style(&i.name).bold().cyan(), i.description))
+        .map(|i| format!("{} - {}{}",
+            style(&i.name).bold().cyan(),
+            i.description,
+            if i.installed { style(" (installed)").dim().to_string() } else { String::new() }
+        ))
         .collect();
 
     let selection = dialoguer::FuzzySelect::new()
@@ -415,7 +1316,7 @@ fn cmd_search() -> Result<()> {
         let chosen = &items[index];
         let skill_id = chosen.skill_path.as_deref().unwrap_or(&chosen.name);
         
-        cmd_add(&chosen.url, skill_id, None)?;
+        cmd_add(ui, scope, &chosen.url, skill_id, None, None, None)?;
     } else {
         outro("Cancelled.")?;
     }
@@ -427,23 +1328,162 @@ fn cmd_search() -> Result<()> {
 // COMMAND: LIST AVAILABLE
 // ============================================================================
 
-fn cmd_list_available(_repo_url: &str, _custom_path: Option<String>) -> Result<()> {
+fn cmd_list_available(scope: &Scope, repo_url: &str, custom_path: Option<String>, reference: Option<&str>) -> Result<()> {
     let spin = spinner();
     spin.start("Discovering available skills...");
+
+    // Listing works without `init`, but honours trusted hosts when configured
+    let client = match load_config(scope) {
+        Ok(config) => http_client(&config)?,
+        Err(_) => SecureHttpClient::new()?,
+    };
+    let listed = reference
+        .map(|reference| client.resolve_commit(repo_url, reference))
+        .transpose()
+        .and_then(|commit| client.list_skills(repo_url, custom_path.as_deref(), commit.as_deref()));
+    let skills = match listed {
+        Ok(skills) => skills,
+        Err(e) => {
+            spin.stop("Discovery failed.");
+            return Err(e);
+        }
+    };
+
     spin.stop("Discovery complete.");
-    
-    log::warning("Skill discovery from remote repos is limited without cloning.")?;
-    log::info("Try installing a specific skill with: skillctl add <url> --skill <name>")?;
-    outro("For full discovery, the repository would need to be cloned locally")?;
-    
+
+    if skills.is_empty() {
+        log::warning("No SKILL.md files found in the repository.")?;
+        return Ok(());
+    }
+
+    log::info(format!("{} available skills:", style(skills.len()).cyan()))?;
+
+    for skill in &skills {
+        println!("   {} {}  {}",
+            style("●").green(),
+            style(&skill.name).bold(),
+            style(skill.description.as_deref().unwrap_or("(no description)")).dim()
+        );
+        println!("     {}", style(&skill.path).dim());
+    }
+    println!();
+
+    outro(format!("Install one with: skillctl add {} --skill <name>", repo_url))?;
+
     Ok(())
 }
+
+// ============================================================================
+// COMMAND: STORE
+// ============================================================================
+
+fn cmd_store(command: &StoreCommands, format: OutputFormat) -> Result<()> {
+    match command {
+        StoreCommands::Gc => {
+            let report = BlobStore::open_default()?.gc()?;
+            if format == OutputFormat::Json {
+                return print_json(&report);
+            }
+
+            if report.removed.is_empty() {
+                log::success(format!("Nothing to collect ({} blobs in use)", report.kept))?;
+            } else {
+                log::success(format!(
+                    "Removed {} unreferenced blob(s), freed {} KiB ({} in use)",
+                    report.removed.len(),
+                    report.freed_bytes.div_ceil(1024),
+                    report.kept
+                ))?;
+            }
+        }
+    }
+    Ok(())
+}
+
+// ============================================================================
+// COMMAND: AUTH
+// ============================================================================
+
+fn cmd_auth(ui: &Interaction, command: &AuthCommands, format: OutputFormat) -> Result<()> {
+    let path = auth::credentials_path()?;
+
+    match command {
+        AuthCommands::Login { host } => {
+            let host = auth::validate_auth_host(host)?;
+            let token = if ui.interactive {
+                cliclack::password(format!("Token for {}", host)).mask('▪').interact()?
+            } else {
+                let mut token = String::new();
+                std::io::stdin().read_line(&mut token)
+                    .context("Failed to read the token from stdin")?;
+                token
+            };
+            let token = token.trim();
+            if token.is_empty() {
+                anyhow::bail!("No token given for {}", host);
+            }
+
+            let mut tokens = auth::load_token_file(&path)?;
+            tokens.insert(host.clone(), token.to_string());
+            auth::save_token_file(&path, &tokens)?;
+            log::success(format!("Saved token for {} to {}", style(&host).green(), path.display()))?;
+
+            // The environment takes precedence over the credential file
+            if let Some((_, source)) = Credentials::load()?.sources().find(|(h, _)| *h == host) {
+                if source != auth::CredentialSource::File {
+                    log::warning(format!("{} is set and overrides the saved token for {}", source, host))?;
+                }
+            }
+        },
+        AuthCommands::Logout { host } => {
+            let host = auth::validate_auth_host(host)?;
+            let mut tokens = auth::load_token_file(&path)?;
+            if tokens.remove(&host).is_some() {
+                auth::save_token_file(&path, &tokens)?;
+                log::success(format!("Removed token for {}", style(&host).green()))?;
+            } else {
+                log::warning(format!("No saved token for {}", host))?;
+            }
+        },
+        AuthCommands::Status => {
+            let credentials = Credentials::load()?;
+            let sources: Vec<(&str, auth::CredentialSource)> = credentials.sources().collect();
+
+            if format == OutputFormat::Json {
+                #[derive(serde::Serialize)]
+                struct TokenListing<'a> {
+                    host: &'a str,
+                    source: String,
+                }
+
+                let listings: Vec<TokenListing> = sources.iter()
+                    .map(|(host, source)| TokenListing { host, source: source.to_string() })
+                    .collect();
+                return print_json(&listings);
+            }
+
+            if sources.is_empty() {
+                log::warning("No tokens configured.")?;
+                outro_note(style("Hint").cyan(), "Set GITHUB_TOKEN or GITLAB_TOKEN, or run 'skillctl auth login <host>'.")?;
+                return Ok(());
+            }
+
+            log::info(format!("Tokens for {} host(s):", style(sources.len()).cyan()))?;
+            for (host, source) in sources {
+                println!("   {} {}  {}", style("●").green(), style(host).bold(), style(format!("from {}", source)).dim());
+            }
+            println!();
+        },
+    }
+    Ok(())
+}
+
 // ============================================================================
 // COMMAND: MEMORY
 // ============================================================================
 
-fn cmd_memory(command: &MemoryCommands) -> Result<()> {
-    let config = load_config()
+fn cmd_memory(scope: &Scope, command: &MemoryCommands, format: OutputFormat) -> Result<()> {
+    let config = load_config(scope)
         .context("Configuration not found. Please run 'skillctl init' first.")?;
     
     // Initialize or load memory store
@@ -467,6 +1507,9 @@ fn cmd_memory(command: &MemoryCommands) -> Result<()> {
         },
         MemoryCommands::List => {
             let memories = memory_store.list_memories();
+            if format == OutputFormat::Json {
+                return print_json(memories);
+            }
             if memories.is_empty() {
                 log::info("No memories found.")?;
             } else {
@@ -485,6 +1528,9 @@ fn cmd_memory(command: &MemoryCommands) -> Result<()> {
         },
         MemoryCommands::Search { query } => {
             let results = memory_store.search_memories(query);
+            if format == OutputFormat::Json {
+                return print_json(&results);
+            }
             if results.is_empty() {
                 log::info("No matching memories found.")?;
             } else {
@@ -510,9 +1556,9 @@ fn cmd_memory(command: &MemoryCommands) -> Result<()> {
         let spin = spinner();
         spin.start("Syncing to editors...");
         
-        for editor in &config.active_editors {
-            if let Err(e) = inject_memory_context(editor, &context) {
-                log::error(format!("Failed to sync memory to {}: {}", editor, e))?;
+        for adapter in config.active_adapters()? {
+            if let Err(e) = adapter.inject_memory(&context) {
+                log::error(format!("Failed to sync memory to {}: {}", adapter.name(), e))?;
             }
         }
         
@@ -521,3 +1567,26 @@ fn cmd_memory(command: &MemoryCommands) -> Result<()> {
 
     Ok(())
 }
+
+/// Open a scope's skill store, allowing the links of its `--dev` skills
+fn open_store(config: &SkillConfig) -> Result<SkillStore> {
+    let linked = config.skills.values()
+        .filter(|entry| entry.dev)
+        .filter_map(|entry| local::source_dir(&entry.url));
+    Ok(SkillStore::new(&config.store_path)?.with_linked_sources(linked))
+}
+
+/// HTTP client that may also reach the self-hosted forges `config` trusts
+fn http_client(config: &SkillConfig) -> Result<SecureHttpClient> {
+    Ok(SecureHttpClient::new()?
+        .with_trusted_hosts(config.effective_trusted_hosts()?)
+        .with_credentials(Credentials::load()?))
+}
+
+/// Print a value as pretty JSON on stdout
+fn print_json<T: serde::Serialize + ?Sized>(value: &T) -> Result<()> {
+    let json = serde_json::to_string_pretty(value)
+        .context("Failed to serialize output")?;
+    println!("{}", json);
+    Ok(())
+}
@@ -0,0 +1,446 @@
+use anyhow::{Result, bail};
+
+pub const BEGIN_MARKER: &str = "<!-- skillctl:begin -->";
+pub const END_MARKER: &str = "<!-- skillctl:end -->";
+const NOTICE: &str = "<!-- Managed by skillctl. Changes inside this block are overwritten. -->";
+const MEMORY_HEADER: &str = "# 🧠 Active Memory Context";
+const LEGACY_MEMORY_TOOLS: &str = "# 🛠️ Memory Tools";
+const LEGACY_NO_MEMORIES: &str = "No memories stored yet.";
+const SKILL_TAG_PREFIX: &str = "<!-- skillctl:skill ";
+const SKILL_TAG_SUFFIX: &str = " -->";
+
+/// An editor config file split around the single block skillctl owns.
+/// Everything outside the block belongs to the user and is written back untouched.
+#[derive(Debug, Default, PartialEq)]
+pub struct ManagedFile {
+    before: String,
+    after: String,
+    has_block: bool,
+    /// Skill references in insertion order: (skill name, rendered reference)
+    skills: Vec<(String, String)>,
+    memory: Option<String>,
+}
+
+impl ManagedFile {
+    pub fn parse(content: &str) -> Result<Self> {
+        let Some(begin) = find_marker_line(content, BEGIN_MARKER, 0) else {
+            if content.contains(END_MARKER) {
+                bail!("Found {} without {}. Fix the file manually.", END_MARKER, BEGIN_MARKER);
+            }
+            return Ok(Self::parse_legacy(content));
+        };
+
+        let inner_start = begin + BEGIN_MARKER.len();
+        let Some(end) = find_marker_line(content, END_MARKER, inner_start) else {
+            bail!("Found {} without {}. Fix the file manually.", BEGIN_MARKER, END_MARKER);
+        };
+
+        let after = &content[end + END_MARKER.len()..];
+        if find_marker_line(after, BEGIN_MARKER, 0).is_some() {
+            bail!("Found more than one skillctl block. Fix the file manually.");
+        }
+
+        let mut file = Self {
+            before: content[..begin].to_string(),
+            after: after.to_string(),
+            has_block: true,
+            ..Default::default()
+        };
+        file.parse_block(&content[inner_start..end]);
+        Ok(file)
+    }
+
+    /// Files written before managed blocks existed: pull out the lines skillctl
+    /// generated so they move into the block, and keep everything else. Only the
+    /// exact formats older versions wrote are migrated; anything that merely looks
+    /// similar stays with the user's content.
+    fn parse_legacy(content: &str) -> Self {
+        let mut file = Self::default();
+        let mut kept = Vec::new();
+        let lines: Vec<&str> = content.lines().collect();
+        let mut i = 0;
+
+        while i < lines.len() {
+            let line = lines[i];
+            if let Some(len) = legacy_memory_len(&lines[i..]) {
+                file.memory = Some(lines[i..i + len].join("\n").trim().to_string());
+                i += len;
+            } else if let Some((name, len)) = legacy_reference(&lines[i..]) {
+                let text: Vec<&str> = lines[i..i + len].iter().map(|l| l.trim()).collect();
+                file.upsert(name, text.join("\n"));
+                i += len;
+            } else {
+                kept.push(line);
+                i += 1;
+            }
+        }
+
+        let mut before = kept.join("\n").trim_end().to_string();
+        if !before.is_empty() {
+            before.push('\n');
+        }
+        file.before = before;
+        file
+    }
+
+    fn parse_block(&mut self, inner: &str) {
+        let mut current: Option<(String, String)> = None;
+        // Inside a tagged reference only the next tag ends it, whatever the lines look like
+        let mut tagged = false;
+        let mut lines = inner.lines();
+
+        while let Some(line) = lines.next() {
+            if line.trim() == MEMORY_HEADER {
+                let rest: Vec<&str> = std::iter::once(line).chain(lines.by_ref()).collect();
+                self.memory = Some(rest.join("\n").trim().to_string());
+                break;
+            }
+
+            if let Some(name) = tag_name(line) {
+                if let Some((name, text)) = current.take() {
+                    self.upsert(name, text);
+                }
+                current = Some((name, line.trim().to_string()));
+                tagged = true;
+            } else if let Some(name) = reference_name(line).filter(|_| !tagged) {
+                if let Some((name, text)) = current.take() {
+                    self.upsert(name, text);
+                }
+                current = Some((name, line.trim().to_string()));
+            } else if let Some((_, text)) = current.as_mut() {
+                if !line.trim().is_empty() {
+                    text.push('\n');
+                    // Tagged references come from user templates: keep their indentation
+                    text.push_str(if tagged { line.trim_end() } else { line.trim() });
+                }
+            }
+        }
+
+        if let Some((name, text)) = current {
+            self.upsert(name, text);
+        }
+    }
+
+    fn upsert(&mut self, name: String, text: String) {
+        match self.skills.iter_mut().find(|(n, _)| *n == name) {
+            Some(existing) => existing.1 = text,
+            None => self.skills.push((name, text)),
+        }
+    }
+
+    /// Prefix arbitrary reference text with a tag naming its skill, so it can be
+    /// found again in the block whatever the text looks like
+    pub fn tagged_reference(name: &str, text: &str) -> String {
+        format!("{}{}{}\n{}", SKILL_TAG_PREFIX, name, SKILL_TAG_SUFFIX, text.trim())
+    }
+
+    /// End a file written for a single skill with the same tag, so it can be
+    /// told apart from the user's own files next to it
+    pub fn tagged_file(name: &str, content: &str) -> String {
+        format!("{}\n\n{}{}{}\n", content.trim_end(), SKILL_TAG_PREFIX, name, SKILL_TAG_SUFFIX)
+    }
+
+    /// Skill a file written by `tagged_file` belongs to
+    pub fn file_tag(content: &str) -> Option<String> {
+        content.lines().rev()
+            .find(|line| !line.trim().is_empty())
+            .and_then(tag_name)
+    }
+
+    /// Add or replace the reference for a skill
+    pub fn set_skill(&mut self, name: &str, reference: &str) {
+        self.upsert(name.to_string(), reference.trim().to_string());
+    }
+
+    /// Remove the reference for a skill, returning whether it was present
+    pub fn remove_skill(&mut self, name: &str) -> bool {
+        let before = self.skills.len();
+        self.skills.retain(|(n, _)| n != name);
+        self.skills.len() != before
+    }
+
+    /// Replace the memory section
+    pub fn set_memory(&mut self, memory: &str) {
+        let memory = memory.trim();
+        self.memory = if memory.is_empty() { None } else { Some(memory.to_string()) };
+    }
+
+    /// Names of the skills referenced in the block
+    pub fn skill_names(&self) -> Vec<String> {
+        self.skills.iter().map(|(name, _)| name.clone()).collect()
+    }
+
+    pub fn render(&self) -> String {
+        let block_empty = self.skills.is_empty() && self.memory.is_none();
+
+        if block_empty {
+            if !self.has_block {
+                return self.before.clone();
+            }
+            // Drop the block and the blank line that separated it from user content
+            let before = self.before.trim_end_matches('\n');
+            let after = self.after.strip_prefix('\n').unwrap_or(&self.after);
+            return match (before.is_empty(), after.is_empty()) {
+                (true, _) => after.to_string(),
+                (false, true) => format!("{}\n", before),
+                (false, false) => format!("{}\n{}", before, after),
+            };
+        }
+
+        let mut block = format!("{}\n{}\n", BEGIN_MARKER, NOTICE);
+        if !self.skills.is_empty() {
+            block.push('\n');
+            for (_, text) in &self.skills {
+                block.push_str(text);
+                block.push('\n');
+            }
+        }
+        if let Some(memory) = &self.memory {
+            block.push('\n');
+            block.push_str(memory);
+            block.push('\n');
+        }
+        block.push_str(END_MARKER);
+
+        if self.has_block {
+            format!("{}{}{}", self.before, block, self.after)
+        } else if self.before.trim().is_empty() {
+            format!("{}\n", block)
+        } else {
+            format!("{}\n\n{}\n", self.before.trim_end(), block)
+        }
+    }
+}
+
+/// Byte offset of `marker` when it sits on a line of its own, searching from `from`
+fn find_marker_line(content: &str, marker: &str, from: usize) -> Option<usize> {
+    let mut offset = from;
+    for line in content[from..].split_inclusive('\n') {
+        if line.trim() == marker {
+            return Some(offset + (line.len() - line.trim_start().len()));
+        }
+        offset += line.len();
+    }
+    None
+}
+
+/// Skill name if `line` is a tag written by `ManagedFile::tagged_reference`
+fn tag_name(line: &str) -> Option<String> {
+    line.trim()
+        .strip_prefix(SKILL_TAG_PREFIX)?
+        .strip_suffix(SKILL_TAG_SUFFIX)
+        .map(|n| n.trim().to_string())
+        .filter(|n| !n.is_empty())
+}
+
+/// Number of lines of the memory section older versions appended, if `lines`
+/// starts with one: the header, memory entries (or the empty notice) and the
+/// memory tools footer. It ends at the first line of any other shape.
+fn legacy_memory_len(lines: &[&str]) -> Option<usize> {
+    if lines.first()?.trim() != MEMORY_HEADER {
+        return None;
+    }
+
+    let mut len = 1;
+    let mut entries = false;
+    let mut in_tools = false;
+    for line in &lines[1..] {
+        let line = line.trim();
+        let generated = if line.is_empty() {
+            true
+        } else if in_tools {
+            line.starts_with("- Save: `skillctl memory ") || line.starts_with("- Delete: `skillctl memory ")
+        } else if line == LEGACY_MEMORY_TOOLS {
+            in_tools = true;
+            true
+        } else {
+            let entry = line == LEGACY_NO_MEMORIES || line.starts_with("- [ID: ");
+            entries |= entry;
+            entry
+        };
+        if !generated {
+            break;
+        }
+        len += 1;
+    }
+
+    // A lone header is the user's own heading
+    if !entries {
+        return None;
+    }
+    // Blank lines before the next user content stay with it
+    while lines[len - 1].trim().is_empty() {
+        len -= 1;
+    }
+    Some(len)
+}
+
+/// Skill name and line count if `lines` starts with a reference in one of the
+/// exact formats older versions wrote, pointing at a SKILL.md:
+/// `- Skill (name) -> Read file: path`, `Running context for name: See path`, or
+/// `### Skill: name` followed by ``Refer to logic in: `path` ``
+fn legacy_reference(lines: &[&str]) -> Option<(String, usize)> {
+    let line = lines.first()?.trim();
+    let is_skill_md = |path: &str| path.ends_with("/SKILL.md") || path == "SKILL.md";
+
+    let (name, path, len) = if let Some(rest) = line.strip_prefix("- Skill (") {
+        let (name, path) = rest.split_once(") -> Read file: ")?;
+        (name, path, 1)
+    } else if let Some(rest) = line.strip_prefix("Running context for ") {
+        let (name, path) = rest.split_once(": See ")?;
+        (name, path, 1)
+    } else if let Some(name) = line.strip_prefix("### Skill: ") {
+        let path = lines.get(1)?.trim()
+            .strip_prefix("Refer to logic in: `")?
+            .strip_suffix('`')?;
+        (name, path, 2)
+    } else {
+        return None;
+    };
+
+    let name = name.trim();
+    if name.is_empty() || name.contains(char::is_whitespace) || !is_skill_md(path.trim()) {
+        return None;
+    }
+    Some((name.to_string(), len))
+}
+
+/// Skill name if `line` starts a reference generated by skillctl
+fn reference_name(line: &str) -> Option<String> {
+    let line = line.trim();
+    let name = if let Some(rest) = line.strip_prefix("### Skill: ") {
+        Some(rest)
+    } else if let Some(rest) = line.strip_prefix("- Skill (") {
+        rest.split(')').next()
+    } else if let Some(rest) = line.strip_prefix("Running context for ") {
+        rest.split(':').next()
+    } else {
+        None
+    };
+
+    name.map(|n| n.trim().to_string()).filter(|n| !n.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const USER_BEFORE: &str = "# My rules\n\n- See the style guide before answering.\n- Read file: docs/ARCH.md\n";
+    const USER_AFTER: &str = "\n## Team notes\nSee CONTRIBUTING.md\n";
+
+    fn add(content: &str, name: &str) -> String {
+        let mut file = ManagedFile::parse(content).unwrap();
+        file.set_skill(name, &format!("- Skill ({}) -> Read file: .skillctl/store/{}/SKILL.md", name, name));
+        file.render()
+    }
+
+    fn remove(content: &str, name: &str) -> String {
+        let mut file = ManagedFile::parse(content).unwrap();
+        file.remove_skill(name);
+        file.render()
+    }
+
+    fn sync_memory(content: &str, memory: &str) -> String {
+        let mut file = ManagedFile::parse(content).unwrap();
+        file.set_memory(memory);
+        file.render()
+    }
+
+    #[test]
+    fn test_user_content_survives_add_remove_and_memory() {
+        let content = add(USER_BEFORE, "python");
+        assert!(content.starts_with(USER_BEFORE));
+        assert_eq!(content.matches(BEGIN_MARKER).count(), 1);
+
+        // User appends content after the block
+        let content = format!("{}{}", content, USER_AFTER);
+
+        let content = add(&content, "rust");
+        let content = sync_memory(&content, "\n# 🧠 Active Memory Context\n\n- [ID: 1] [stack] (Prio: 5) Uses Rust\n\n# 🛠️ Memory Tools\n- Delete: `skillctl memory forget ID`\n");
+        let content = sync_memory(&content, "\n# 🧠 Active Memory Context\n\nNo memories stored yet.\n");
+        let content = remove(&content, "python");
+
+        assert!(content.starts_with(USER_BEFORE));
+        assert!(content.ends_with(USER_AFTER));
+        assert!(content.contains("- Skill (rust)"));
+        assert!(!content.contains("- Skill (python)"));
+        assert!(content.contains("No memories stored yet."));
+        assert!(!content.contains("Uses Rust"));
+        assert_eq!(content.matches(BEGIN_MARKER).count(), 1);
+        assert_eq!(content.matches(END_MARKER).count(), 1);
+
+        // Removing the last managed item removes the block and restores the file
+        let content = remove(&content, "rust");
+        let content = sync_memory(&content, "");
+        assert_eq!(content, format!("{}{}", USER_BEFORE, USER_AFTER));
+    }
+
+    #[test]
+    fn test_block_is_rewritten_in_place() {
+        let content = format!("{}\n{}\n- Skill (a) -> Read file: x\n{}\n{}", USER_BEFORE, BEGIN_MARKER, END_MARKER, USER_AFTER);
+        let updated = add(&content, "b");
+
+        assert!(updated.starts_with(&format!("{}\n{}", USER_BEFORE, BEGIN_MARKER)));
+        assert!(updated.ends_with(&format!("{}\n{}", END_MARKER, USER_AFTER)));
+        let names = ManagedFile::parse(&updated).unwrap().skill_names();
+        assert_eq!(names, vec!["a", "b"]);
+    }
+
+    #[test]
+    fn test_multiline_references() {
+        let mut file = ManagedFile::parse("").unwrap();
+        file.set_skill("a", "### Skill: a\nRefer to logic in: `x`");
+        file.set_skill("b", "### Skill: b\nRefer to logic in: `y`");
+        let rendered = file.render();
+
+        let mut file = ManagedFile::parse(&rendered).unwrap();
+        assert_eq!(file.skill_names(), vec!["a", "b"]);
+        file.remove_skill("a");
+        let rendered = file.render();
+        assert!(!rendered.contains("`x`"));
+        assert!(rendered.contains("### Skill: b\nRefer to logic in: `y`"));
+    }
+
+    #[test]
+    fn test_legacy_content_is_migrated() {
+        let legacy = "# AI Rules for Windsurf\n\n- Skill (memory) -> Read file: .skillctl/store/memory/SKILL.md\n\n# 🧠 Active Memory Context\n\nNo memories stored yet.\n\n# 🛠️ Memory Tools\n- Save: `skillctl memory learn \"text\"`\n- Delete: `skillctl memory forget ID`\n\n## Mine\nKeep me\n";
+        let file = ManagedFile::parse(legacy).unwrap();
+        assert_eq!(file.skill_names(), vec!["memory"]);
+
+        let rendered = file.render();
+        assert!(rendered.starts_with("# AI Rules for Windsurf\n"));
+        assert!(rendered.contains("## Mine\nKeep me"));
+        assert_eq!(rendered.matches("- Skill (memory)").count(), 1);
+        assert_eq!(rendered.matches(MEMORY_HEADER).count(), 1);
+        assert!(rendered.find("Keep me").unwrap() < rendered.find(BEGIN_MARKER).unwrap());
+    }
+
+    #[test]
+    fn test_legacy_migration_leaves_lookalikes_alone() {
+        // A memory header without the generated footer stops at the next heading;
+        // user lines that resemble references but not the generated formats stay put
+        let legacy = "# Rules\n\n### Skill: testing\nAlways write tests first.\n\n- Skill (review) is something we value\n\n# 🧠 Active Memory Context\n\n- [ID: 1] [stack] (Prio: 5) Uses Rust\n\n## Deployment\nShip on Fridays.\n- Delete: nothing\n";
+        let file = ManagedFile::parse(legacy).unwrap();
+        assert!(file.skill_names().is_empty());
+        assert_eq!(file.memory.as_deref(), Some("# 🧠 Active Memory Context\n\n- [ID: 1] [stack] (Prio: 5) Uses Rust"));
+
+        let rendered = file.render();
+        for user_line in ["### Skill: testing\nAlways write tests first.", "- Skill (review) is something we value", "## Deployment\nShip on Fridays.\n- Delete: nothing"] {
+            assert!(rendered[..rendered.find(BEGIN_MARKER).unwrap()].contains(user_line), "{}", user_line);
+        }
+
+        // A heading that happens to match the memory header is not a memory section
+        let heading = "# 🧠 Active Memory Context\nMy own notes about memory.\n";
+        let file = ManagedFile::parse(heading).unwrap();
+        assert!(file.memory.is_none());
+        assert_eq!(file.render(), heading);
+    }
+
+    #[test]
+    fn test_broken_markers_are_rejected() {
+        assert!(ManagedFile::parse(&format!("{}\nno end\n", BEGIN_MARKER)).is_err());
+        assert!(ManagedFile::parse(&format!("no begin\n{}\n", END_MARKER)).is_err());
+        let twice = format!("{b}\n{e}\n{b}\n{e}\n", b = BEGIN_MARKER, e = END_MARKER);
+        assert!(ManagedFile::parse(&twice).is_err());
+    }
+}
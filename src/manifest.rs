@@ -0,0 +1,120 @@
+use serde::{Deserialize, Serialize};
+use anyhow::{Result, Context, bail};
+use crate::security::validate_skill_name;
+
+/// Metadata declared in the YAML frontmatter of a SKILL.md
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct SkillManifest {
+    /// Skill name (defaults to the skill directory name)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// What the skill does (required)
+    #[serde(default)]
+    pub description: String,
+    /// Tools the skill expects the agent to use
+    #[serde(default, deserialize_with = "string_or_list", skip_serializing_if = "Vec::is_empty")]
+    pub tools: Vec<String>,
+    /// File patterns the skill applies to
+    #[serde(default, deserialize_with = "string_or_list", skip_serializing_if = "Vec::is_empty")]
+    pub globs: Vec<String>,
+    /// Whether the skill should be loaded for every request
+    #[serde(default, rename = "alwaysApply", skip_serializing_if = "Option::is_none")]
+    pub always_apply: Option<bool>,
+}
+
+impl SkillManifest {
+    /// Parse and validate the frontmatter of a SKILL.md
+    pub fn parse(content: &str) -> Result<Self> {
+        let frontmatter = extract_frontmatter(content)
+            .context("SKILL.md has no YAML frontmatter (expected a '---' block with at least a description)")?;
+
+        let manifest: SkillManifest = serde_yaml::from_str(frontmatter)
+            .context("Failed to parse SKILL.md frontmatter")?;
+
+        if manifest.description.trim().is_empty() {
+            bail!("SKILL.md frontmatter is missing the required 'description' field");
+        }
+
+        if let Some(name) = &manifest.name {
+            validate_skill_name(name)
+                .context("Invalid 'name' in SKILL.md frontmatter")?;
+        }
+
+        Ok(manifest)
+    }
+}
+
+/// Return the YAML between the opening and closing `---` lines
+fn extract_frontmatter(content: &str) -> Option<&str> {
+    let content = content.strip_prefix('\u{feff}').unwrap_or(content);
+    let body = content.strip_prefix("---")?;
+    let body = body.strip_prefix("\r\n").or_else(|| body.strip_prefix('\n'))?;
+
+    let mut offset = 0;
+    for line in body.split_inclusive('\n') {
+        if line.trim_end() == "---" {
+            return Some(&body[..offset]);
+        }
+        offset += line.len();
+    }
+    None
+}
+
+/// Accept either `key: a, b` or a YAML list for list-like fields
+fn string_or_list<'de, D>(deserializer: D) -> std::result::Result<Vec<String>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum StringOrList {
+        One(String),
+        Many(Vec<String>),
+    }
+
+    Ok(match Option::<StringOrList>::deserialize(deserializer)? {
+        None => Vec::new(),
+        Some(StringOrList::One(value)) => value.split(',')
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+            .collect(),
+        Some(StringOrList::Many(values)) => values,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_manifest() {
+        let content = "---\nname: python-async\ndescription: \"Async Python patterns\"\ntools: bash(skillctl memory)\nglobs:\n  - \"*.py\"\n  - \"*.pyi\"\nalwaysApply: false\n---\n# Body\ndescription: ignored\n";
+        let manifest = SkillManifest::parse(content).unwrap();
+
+        assert_eq!(manifest.name.as_deref(), Some("python-async"));
+        assert_eq!(manifest.description, "Async Python patterns");
+        assert_eq!(manifest.tools, vec!["bash(skillctl memory)"]);
+        assert_eq!(manifest.globs, vec!["*.py", "*.pyi"]);
+        assert_eq!(manifest.always_apply, Some(false));
+    }
+
+    #[test]
+    fn test_parse_manifest_comma_separated_globs() {
+        let manifest = SkillManifest::parse("---\ndescription: TS\nglobs: \"*.ts, *.tsx\"\n---\n").unwrap();
+        assert_eq!(manifest.globs, vec!["*.ts", "*.tsx"]);
+        assert!(manifest.name.is_none());
+    }
+
+    #[test]
+    fn test_parse_manifest_errors() {
+        // No frontmatter
+        assert!(SkillManifest::parse("# Just markdown").is_err());
+        // Unterminated frontmatter
+        assert!(SkillManifest::parse("---\ndescription: x\n# Body").is_err());
+        // Missing description
+        let err = SkillManifest::parse("---\nname: skill\n---\n").unwrap_err();
+        assert!(err.to_string().contains("description"));
+        // Invalid name
+        assert!(SkillManifest::parse("---\nname: ../etc\ndescription: x\n---\n").is_err());
+    }
+}
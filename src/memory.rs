@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
 use std::fs;
 use std::path::{Path, PathBuf};
 use anyhow::{Result, Context, bail};
@@ -101,11 +102,7 @@ impl MemoryStore {
     }
 
     pub fn list_memories(&mut self) -> &Vec<MemoryEntry> {
-        // Sort by priority (descending) then by date (descending)
-        self.memories.sort_by(|a, b| {
-            b.priority.cmp(&a.priority)
-                .then_with(|| b.created_at.cmp(&a.created_at))
-        });
+        self.memories.sort_by(by_relevance);
         &self.memories
     }
     
@@ -116,19 +113,18 @@ impl MemoryStore {
             .collect();
         
         // Sort search results by priority as well
-        results.sort_by(|a, b| {
-            b.priority.cmp(&a.priority)
-                .then_with(|| b.created_at.cmp(&a.created_at))
-        });
+        results.sort_by(|a, b| by_relevance(a, b));
         
         results
     }
     
     /// Format memories for injection into AI context
-    pub fn to_context_string(&mut self) -> String {
+    pub fn to_context_string(&self) -> String {
         let mut output = String::from("\n# 🧠 Active Memory Context\n\n");
         
-        let memories = self.list_memories(); // Uses the sorted list
+        // Same ordering as list_memories, without mutating the store
+        let mut memories: Vec<&MemoryEntry> = self.memories.iter().collect();
+        memories.sort_by(|a, b| by_relevance(a, b));
         
         if memories.is_empty() {
             output.push_str("No memories stored yet.\n");
@@ -151,6 +147,12 @@ impl MemoryStore {
     }
 }
 
+/// Memory order everywhere: by priority (descending) then by date (descending)
+fn by_relevance(a: &MemoryEntry, b: &MemoryEntry) -> Ordering {
+    b.priority.cmp(&a.priority)
+        .then_with(|| b.created_at.cmp(&a.created_at))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,70 @@
+//! Local HTTP server for tests that exercise real requests
+
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use tiny_http::{Header, Request, Response, ResponseBox, Server};
+
+/// Serve every request on a local port with `handler`. Returns the base URL
+/// (`http://localhost:<port>`), which `validate_url` accepts.
+pub fn serve<F>(handler: F) -> String
+where
+    F: Fn(&Request) -> ResponseBox + Send + 'static,
+{
+    let server = Server::http("127.0.0.1:0").expect("Failed to start mock server");
+    let port = server.server_addr().to_ip().expect("Mock server has no TCP address").port();
+
+    std::thread::spawn(move || {
+        for request in server.incoming_requests() {
+            let response = handler(&request);
+            let _ = request.respond(response);
+        }
+    });
+
+    format!("http://localhost:{}", port)
+}
+
+/// Answer every connection with the same raw HTTP response, for responses
+/// tiny_http will not produce (a lying Content-Length, hand-made chunking)
+pub fn serve_raw(response: Vec<u8>) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("Failed to start mock server");
+    let port = listener.local_addr().expect("Mock server has no address").port();
+
+    std::thread::spawn(move || {
+        for mut stream in listener.incoming().flatten() {
+            // Read the request head before answering
+            let mut head = Vec::new();
+            let mut byte = [0u8; 1];
+            while !head.ends_with(b"\r\n\r\n") && matches!(stream.read(&mut byte), Ok(1)) {
+                head.push(byte[0]);
+            }
+            // The client may hang up early on purpose
+            let _ = stream.write_all(&response);
+        }
+    });
+
+    format!("http://localhost:{}", port)
+}
+
+/// Serve fixed `(path and query, content type, body)` routes; anything else is a 404
+pub fn serve_routes(routes: Vec<(String, &'static str, String)>) -> String {
+    serve(move |request| {
+        match routes.iter().find(|(url, _, _)| url == request.url()) {
+            Some((_, content_type, body)) => respond(content_type, body),
+            None => Response::from_string("not found").with_status_code(404).boxed(),
+        }
+    })
+}
+
+/// A 200 response with the given content type
+pub fn respond(content_type: &str, body: &str) -> ResponseBox {
+    Response::from_string(body)
+        .with_header(Header::from_bytes("Content-Type", content_type).unwrap())
+        .boxed()
+}
+
+/// The value of a request header, if present
+pub fn header<'a>(request: &'a Request, name: &'static str) -> Option<&'a str> {
+    request.headers().iter()
+        .find(|header| header.field.equiv(name))
+        .map(|header| header.value.as_str())
+}
@@ -1,15 +1,52 @@
 use anyhow::{Result, Context, bail};
-use reqwest::blocking::Client;
+use reqwest::blocking::{Client, Response};
+use serde::de::DeserializeOwned;
+use std::io::Read;
 use std::time::Duration;
 
-use crate::security::{validate_url, validate_skill_content};
+use crate::security::{validate_url, validate_skill_content, validate_git_ref, is_binary_file_name, MAX_SKILL_FILES, MAX_SKILL_TOTAL_BYTES};
+use crate::store::SkillFile;
+use crate::manifest::SkillManifest;
+use crate::providers::{SourceProvider, TrustedHosts, provider_for};
+use crate::auth::Credentials;
 
 const USER_AGENT: &str = concat!("skillctl/", env!("CARGO_PKG_VERSION"));
 const TIMEOUT_SECS: u64 = 30;
 const MAX_REDIRECTS: usize = 5;
+const MAX_API_RESPONSE_BYTES: u64 = 10_000_000;
+/// Largest single file `download` accepts (matches `validate_skill_content`)
+const MAX_DOWNLOAD_BYTES: u64 = 1_000_000;
+
+/// Directories (relative to the repository root) that contain one folder per skill.
+/// `*` matches exactly one path segment.
+pub(crate) const SKILL_LAYOUTS: &[&str] = &[
+    "skills",
+    "plugins/*/skills",
+    ".agent/skills",
+    ".cursor/skills",
+    ".windsurf/skills",
+];
+
+/// A skill found in a remote repository
+#[derive(Debug, Clone)]
+pub struct RemoteSkill {
+    pub name: String,
+    pub path: String,
+    pub description: Option<String>,
+}
+
+/// A SKILL.md fetched from a repository, along with where it was found
+#[derive(Debug, Clone)]
+pub struct FetchedSkill {
+    pub content: String,
+    pub path: String,
+    pub branch: String,
+}
 
 pub struct SecureHttpClient {
     client: Client,
+    trusted_hosts: TrustedHosts,
+    credentials: Credentials,
 }
 
 impl SecureHttpClient {
@@ -21,25 +58,68 @@ impl SecureHttpClient {
             .build()
             .context("Failed to create HTTP client")?;
 
-        Ok(Self { client })
+        Ok(Self { client, trusted_hosts: TrustedHosts::new(), credentials: Credentials::default() })
     }
 
-    /// Download content from a URL with security validations
-    pub fn download(&self, url: &str) -> Result<String> {
+    /// Also allow the given self-hosted forges
+    pub fn with_trusted_hosts(mut self, trusted_hosts: TrustedHosts) -> Self {
+        self.trusted_hosts = trusted_hosts;
+        self
+    }
+
+    /// Send tokens for private repositories, each only to its own host
+    pub fn with_credentials(mut self, credentials: Credentials) -> Self {
+        self.credentials = credentials;
+        self
+    }
+
+    /// Validate the URL, send a GET request and check the status code
+    fn get(&self, url: &str) -> Result<Response> {
         // Validate URL before making request
-        let validated_url = validate_url(url)?;
+        let trusted: Vec<&str> = self.trusted_hosts.keys().map(String::as_str).collect();
+        let validated_url = validate_url(url, &trusted)?;
+
+        // Authenticate only to the host the token was given for; reqwest drops
+        // the header if a redirect leaves that host
+        let token = validated_url.host_str().and_then(|host| self.credentials.token_for(host));
+        let mut request = self.client.get(validated_url.as_str());
+        if let Some(token) = token {
+            request = request.bearer_auth(token);
+        }
 
         // Make the request
-        let response = self.client
-            .get(validated_url.as_str())
+        let response = request
             .send()
+            .map_err(|e| anyhow::anyhow!(self.credentials.redact(&e.to_string())))
             .context("Failed to send HTTP request")?;
 
         // Check status code
-        if !response.status().is_success() {
-            bail!("HTTP request failed with status: {}", response.status());
+        let status = response.status();
+        if !status.is_success() {
+            if token.is_none() && (status.as_u16() == 401 || status.as_u16() == 403) {
+                bail!("HTTP request failed with status: {} (for a private repository, set GITHUB_TOKEN or GITLAB_TOKEN, or run 'skillctl auth login {}')",
+                    status, validated_url.host_str().unwrap_or_default());
+            }
+            bail!("HTTP request failed with status: {}", status);
         }
 
+        Ok(response)
+    }
+
+    /// Fetch and deserialize a JSON document (used for forge APIs)
+    pub(crate) fn get_json<T: DeserializeOwned>(&self, url: &str) -> Result<T> {
+        let response = self.get(url)?;
+        let body = read_body(response, MAX_API_RESPONSE_BYTES)
+            .context("API response rejected")?;
+
+        serde_json::from_slice(&body)
+            .context("Failed to parse API response")
+    }
+
+    /// Download content from a URL with security validations
+    pub fn download(&self, url: &str) -> Result<String> {
+        let response = self.get(url)?;
+
         // Check content type (should be text)
         if let Some(content_type) = response.headers().get("content-type") {
             let content_type_str = content_type.to_str().unwrap_or("");
@@ -50,16 +130,10 @@ impl SecureHttpClient {
             }
         }
 
-        // Check content length (prevent DoS)
-        if let Some(content_length) = response.content_length() {
-            if content_length > 1_000_000 {  // 1MB limit
-                bail!("Content too large: {} bytes (max 1MB)", content_length);
-            }
-        }
-
-        // Download content
-        let content = response.text()
-            .context("Failed to read response body")?;
+        // Download content, never holding more than the limit (prevent DoS)
+        let body = read_body(response, MAX_DOWNLOAD_BYTES)?;
+        let content = String::from_utf8(body)
+            .map_err(|e| anyhow::anyhow!("Content is not valid UTF-8 (invalid byte at offset {})", e.utf8_error().valid_up_to()))?;
 
         // Validate content
         validate_skill_content(&content)?;
@@ -67,14 +141,30 @@ impl SecureHttpClient {
         Ok(content)
     }
 
-    /// Try multiple paths to find a skill file
-    pub fn find_skill(&self, repo_url: &str, skill_name: &str, custom_path: Option<String>) -> Result<(String, String)> {
-        // Transform GitHub URL to raw URL
-        let raw_base = repo_url
-            .replace("github.com", "raw.githubusercontent.com")
-            .trim_end_matches('/')
-            .to_string();
+    /// The provider serving a repository URL
+    fn provider(&self, repo_url: &str) -> Result<Box<dyn SourceProvider>> {
+        provider_for(repo_url, &self.trusted_hosts)
+    }
 
+    /// Branches to try when no ref was requested: the default branch reported by
+    /// the host's API, or `main` and `master` if the API cannot be reached
+    fn candidate_branches(&self, provider: &dyn SourceProvider, reference: Option<&str>) -> Vec<String> {
+        match reference {
+            Some(reference) => vec![reference.to_string()],
+            None => match provider.default_branch(self) {
+                Ok(branch) => vec![branch],
+                Err(_) => vec!["main".to_string(), "master".to_string()],
+            },
+        }
+    }
+
+    /// Try multiple paths to find a skill file.
+    /// When `reference` is given only that commit/branch is tried, otherwise the default branch.
+    pub fn find_skill(&self, repo_url: &str, skill_name: &str, custom_path: Option<String>, reference: Option<&str>) -> Result<FetchedSkill> {
+        self.find_skill_in(self.provider(repo_url)?.as_ref(), skill_name, custom_path, reference)
+    }
+
+    pub(crate) fn find_skill_in(&self, provider: &dyn SourceProvider, skill_name: &str, custom_path: Option<String>, reference: Option<&str>) -> Result<FetchedSkill> {
         // Determine paths to try (in order of priority)
         let paths_to_try: Vec<String> = if let Some(custom) = custom_path {
             // If custom path provided, only try that
@@ -95,18 +185,24 @@ impl SecureHttpClient {
             ]
         };
 
+        let branches = self.candidate_branches(provider, reference);
+
         // Try each path until one works
         let mut tried_urls = Vec::new();
 
         for path_in_repo in paths_to_try {
-            // Try both 'main' and 'master' branches
-            for branch in &["main", "master"] {
-                let target_url = format!("{}/{}/{}", raw_base, branch, path_in_repo);
+            // Try each candidate branch
+            for branch in &branches {
+                let target_url = provider.raw_url(branch, &path_in_repo);
                 tried_urls.push(target_url.clone());
                 
                 match self.download(&target_url) {
                     Ok(content) => {
-                        return Ok((content, path_in_repo));
+                        return Ok(FetchedSkill {
+                            content,
+                            path: path_in_repo,
+                            branch: branch.to_string(),
+                        });
                     }
                     Err(_) => {
                         // Continue to next branch/path
@@ -120,6 +216,214 @@ impl SecureHttpClient {
             tried_urls.len(),
             tried_urls.last().unwrap_or(&"none".to_string()))
     }
+
+    /// Resolve a branch, tag or (abbreviated) commit of a repository to its commit SHA
+    pub fn resolve_commit(&self, repo_url: &str, reference: &str) -> Result<String> {
+        validate_git_ref(reference)?;
+        self.provider(repo_url)?.resolve_commit(self, reference)
+    }
+
+    /// Build the raw URL of a file in a repository at a given commit, branch or tag
+    pub fn raw_file_url(&self, repo_url: &str, reference: &str, path: &str) -> Result<String> {
+        Ok(self.provider(repo_url)?.raw_url(reference, path))
+    }
+
+    /// Download every file of the skill directory containing `skill_md_path`.
+    /// Paths in the result are relative to that directory.
+    pub fn fetch_skill_dir(&self, repo_url: &str, reference: &str, skill_md_path: &str) -> Result<Vec<SkillFile>> {
+        self.fetch_skill_dir_in(self.provider(repo_url)?.as_ref(), reference, skill_md_path)
+    }
+
+    pub(crate) fn fetch_skill_dir_in(&self, provider: &dyn SourceProvider, reference: &str, skill_md_path: &str) -> Result<Vec<SkillFile>> {
+        // A SKILL.md at the repository root would pull in the whole repository
+        let Some((skill_dir, _)) = skill_md_path.rsplit_once('/') else {
+            let content = self.download(&provider.raw_url(reference, skill_md_path))?;
+            return Ok(vec![SkillFile::new("SKILL.md", content)]);
+        };
+
+        let prefix = format!("{}/", skill_dir);
+        let paths: Vec<String> = provider.list_files(self, reference)?
+            .into_iter()
+            .filter(|path| path.starts_with(&prefix))
+            .collect();
+
+        if paths.len() > MAX_SKILL_FILES {
+            bail!("Skill directory has too many files ({} > {})", paths.len(), MAX_SKILL_FILES);
+        }
+
+        // Every file is part of the skill's hash: refuse the skill rather than drop one
+        let binary: Vec<&str> = paths.iter()
+            .map(|path| &path[prefix.len()..])
+            .filter(|path| is_binary_file_name(path))
+            .collect();
+        if !binary.is_empty() {
+            bail!("Skill {} contains binary files, which are not supported: {}", skill_dir, binary.join(", "));
+        }
+
+        let mut files = Vec::new();
+        let mut total_size = 0;
+        for path in paths {
+            let content = self.download(&provider.raw_url(reference, &path))
+                .with_context(|| format!("Failed to download {}", path))?;
+
+            total_size += content.len();
+            if total_size > MAX_SKILL_TOTAL_BYTES {
+                bail!("Skill directory is too large (max {} bytes)", MAX_SKILL_TOTAL_BYTES);
+            }
+
+            files.push(SkillFile::new(&path[prefix.len()..], content));
+        }
+
+        if !files.iter().any(|f| f.path == "SKILL.md") {
+            bail!("SKILL.md not found in {} at {}", skill_dir, reference);
+        }
+
+        Ok(files)
+    }
+
+    /// List every skill published in a repository, without installing anything.
+    /// When `reference` is given only that commit/branch is listed, otherwise the default branch.
+    pub fn list_skills(&self, repo_url: &str, custom_path: Option<&str>, reference: Option<&str>) -> Result<Vec<RemoteSkill>> {
+        self.list_skills_in(self.provider(repo_url)?.as_ref(), custom_path, reference)
+            .with_context(|| format!("Failed to list files in {}", repo_url))
+    }
+
+    pub(crate) fn list_skills_in(&self, provider: &dyn SourceProvider, custom_path: Option<&str>, reference: Option<&str>) -> Result<Vec<RemoteSkill>> {
+        let mut last_error = None;
+        let mut found = None;
+        for branch in self.candidate_branches(provider, reference) {
+            match provider.list_files(self, &branch) {
+                Ok(files) => {
+                    found = Some((branch, files));
+                    break;
+                }
+                Err(e) => last_error = Some(e),
+            }
+        }
+
+        let Some((branch, files)) = found else {
+            return Err(last_error.unwrap_or_else(|| anyhow::anyhow!("No branch found")));
+        };
+
+        let mut skills: Vec<RemoteSkill> = files.iter()
+            .filter_map(|path| {
+                let name = match custom_path {
+                    Some(prefix) => skill_name_under_prefix(path, prefix)?,
+                    None => skill_name_from_path(path)?,
+                };
+                Some(RemoteSkill { name, path: path.clone(), description: None })
+            })
+            .collect();
+
+        skills.sort_by(|a, b| a.name.cmp(&b.name).then_with(|| a.path.cmp(&b.path)));
+
+        // Fetch each SKILL.md to read its description (best effort)
+        for skill in &mut skills {
+            let raw_url = provider.raw_url(&branch, &skill.path);
+            if let Ok(content) = self.download(&raw_url) {
+                skill.description = SkillManifest::parse(&content).ok().map(|m| m.description);
+            }
+        }
+
+        Ok(skills)
+    }
+}
+
+/// Read a response body, failing as soon as it grows past `max_bytes`.
+/// Content-Length is only trusted to reject early: chunked responses have none,
+/// and a server may send more than it announced.
+fn read_body(response: Response, max_bytes: u64) -> Result<Vec<u8>> {
+    if let Some(content_length) = response.content_length() {
+        if content_length > max_bytes {
+            bail!("Content too large: {} bytes (max {})", content_length, max_bytes);
+        }
+    }
+
+    let mut body = Vec::new();
+    response.take(max_bytes + 1).read_to_end(&mut body)
+        .context("Failed to read response body")?;
+    if body.len() as u64 > max_bytes {
+        bail!("Content too large: more than {} bytes", max_bytes);
+    }
+    Ok(body)
+}
+
+/// Split a ref specifier off a repository URL: `<url>@<tag-or-sha>` or `<url>#<branch>`
+pub fn split_repo_ref(spec: &str) -> (String, Option<String>) {
+    if let Some((repo, reference)) = spec.split_once('#') {
+        return (repo.to_string(), Some(reference.to_string()));
+    }
+
+    // An '@' before the path is URL userinfo, not a ref
+    let host_start = spec.find("://").map(|i| i + 3).unwrap_or(0);
+    let path_start = spec[host_start..].find('/').map(|i| host_start + i).unwrap_or(spec.len());
+    match spec[path_start..].find('@') {
+        Some(at) => {
+            let at = path_start + at;
+            (spec[..at].to_string(), Some(spec[at + 1..].to_string()))
+        }
+        None => (spec.to_string(), None),
+    }
+}
+
+/// Split a qualified skill id (`owner/repo:skill`) into a GitHub repository URL and skill name
+pub fn parse_qualified_id(id: &str) -> Option<(String, String)> {
+    if id.contains("://") {
+        return None;
+    }
+    let (repo, skill) = id.split_once(':')?;
+    let segments: Vec<&str> = repo.split('/').collect();
+    if segments.len() != 2 || segments.iter().any(|s| s.is_empty()) || skill.is_empty() {
+        return None;
+    }
+    Some((format!("https://github.com/{}", repo), skill.to_string()))
+}
+
+/// Return the skill name if `path` is a SKILL.md inside one of the known layouts
+fn skill_name_from_path(path: &str) -> Option<String> {
+    let segments: Vec<&str> = path.split('/').collect();
+    let (file, rest) = segments.split_last()?;
+    if *file != "SKILL.md" {
+        return None;
+    }
+    let (name, dir) = rest.split_last()?;
+
+    let matches_layout = SKILL_LAYOUTS.iter().any(|layout| {
+        let pattern: Vec<&str> = layout.split('/').collect();
+        pattern.len() == dir.len()
+            && pattern.iter().zip(dir).all(|(p, d)| *p == "*" || p == d)
+    });
+
+    if matches_layout {
+        Some(name.to_string())
+    } else {
+        None
+    }
+}
+
+/// Return the skill name if `path` is a SKILL.md anywhere under `prefix`
+fn skill_name_under_prefix(path: &str, prefix: &str) -> Option<String> {
+    let prefix = prefix.trim_matches('/');
+
+    // --path may point straight at a SKILL.md
+    if path == prefix {
+        return skill_name_from_path(path)
+            .or_else(|| path.rsplit('/').nth(1).map(|s| s.to_string()));
+    }
+
+    let relative = if prefix.is_empty() {
+        path
+    } else {
+        path.strip_prefix(prefix)?.strip_prefix('/')?
+    };
+    let parent = relative.strip_suffix("SKILL.md")?.trim_end_matches('/');
+
+    if parent.is_empty() {
+        // SKILL.md sits directly in the prefix directory
+        prefix.rsplit('/').next().filter(|s| !s.is_empty()).map(|s| s.to_string())
+    } else {
+        parent.rsplit('/').next().map(|s| s.to_string())
+    }
 }
 
 impl Default for SecureHttpClient {
@@ -131,6 +435,10 @@ impl Default for SecureHttpClient {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::auth::CredentialSource;
+    use crate::mock_server;
+    use std::sync::{Arc, Mutex};
+    use tiny_http::{Header, Response};
 
     #[test]
     fn test_client_creation() {
@@ -144,4 +452,149 @@ mod tests {
         let result = client.download("https://192.168.1.1/test");
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_split_repo_ref() {
+        let split = split_repo_ref;
+        assert_eq!(split("https://github.com/user/repo"), ("https://github.com/user/repo".to_string(), None));
+        assert_eq!(split("https://github.com/user/repo@v1.2.0"), ("https://github.com/user/repo".to_string(), Some("v1.2.0".to_string())));
+        assert_eq!(split("https://github.com/user/repo#feature/x"), ("https://github.com/user/repo".to_string(), Some("feature/x".to_string())));
+        assert_eq!(split("https://bot@gitlab.com/group/repo"), ("https://bot@gitlab.com/group/repo".to_string(), None));
+        assert_eq!(split("user/repo@abc1234"), ("user/repo".to_string(), Some("abc1234".to_string())));
+    }
+
+    #[test]
+    fn test_qualified_ids() {
+        assert_eq!(
+            parse_qualified_id("user/repo:memory"),
+            Some(("https://github.com/user/repo".to_string(), "memory".to_string()))
+        );
+        assert_eq!(parse_qualified_id("https://github.com/user/repo"), None);
+        assert_eq!(parse_qualified_id("user:memory"), None);
+        assert_eq!(parse_qualified_id("user/repo:"), None);
+    }
+
+    #[test]
+    fn test_skill_name_from_path() {
+        assert_eq!(skill_name_from_path("skills/memory/SKILL.md").as_deref(), Some("memory"));
+        assert_eq!(skill_name_from_path("plugins/python/skills/async/SKILL.md").as_deref(), Some("async"));
+        assert_eq!(skill_name_from_path(".agent/skills/x/SKILL.md").as_deref(), Some("x"));
+        assert_eq!(skill_name_from_path("skills/memory/README.md"), None);
+        assert_eq!(skill_name_from_path("docs/skills/memory/SKILL.md"), None);
+        assert_eq!(skill_name_from_path("SKILL.md"), None);
+
+        assert_eq!(skill_name_under_prefix("custom/a/SKILL.md", "custom").as_deref(), Some("a"));
+        assert_eq!(skill_name_under_prefix("custom/a/SKILL.md", "custom/a/SKILL.md").as_deref(), Some("a"));
+        assert_eq!(skill_name_under_prefix("other/a/SKILL.md", "custom"), None);
+    }
+
+    #[test]
+    fn test_token_sent_only_to_its_host() {
+        // Receives the redirect below and records the Authorization header it gets
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let recorded = seen.clone();
+        let other = mock_server::serve(move |request| {
+            recorded.lock().unwrap().push(mock_server::header(request, "Authorization").map(str::to_string));
+            mock_server::respond("text/plain", "# Public")
+        });
+
+        let private = mock_server::serve(move |request| {
+            if mock_server::header(request, "Authorization") != Some("Bearer s3cret-token") {
+                return Response::from_string("unauthorized").with_status_code(401).boxed();
+            }
+            match request.url() {
+                "/redirect" => Response::from_string("")
+                    .with_status_code(302)
+                    .with_header(Header::from_bytes("Location", format!("{}/SKILL.md", other)).unwrap())
+                    .boxed(),
+                _ => mock_server::respond("text/plain", "# Private"),
+            }
+        });
+
+        // Without a token for the host the request is refused, with a hint
+        let anonymous = SecureHttpClient::new().unwrap();
+        let err = anonymous.download(&format!("{}/SKILL.md", private)).unwrap_err();
+        assert!(err.to_string().contains("401"));
+        assert!(err.to_string().contains("skillctl auth login localhost"));
+
+        // A token for another host is not sent
+        let mut credentials = Credentials::default();
+        credentials.insert("github.com", "s3cret-token", CredentialSource::Env("GITHUB_TOKEN"));
+        let github_only = SecureHttpClient::new().unwrap().with_credentials(credentials);
+        assert!(github_only.download(&format!("{}/SKILL.md", private)).is_err());
+
+        let mut credentials = Credentials::default();
+        credentials.insert("localhost", "s3cret-token", CredentialSource::File);
+        let client = SecureHttpClient::new().unwrap().with_credentials(credentials);
+        assert_eq!(client.download(&format!("{}/SKILL.md", private)).unwrap(), "# Private");
+
+        // Following a redirect to another origin drops the token
+        assert_eq!(client.download(&format!("{}/redirect", private)).unwrap(), "# Public");
+        assert_eq!(*seen.lock().unwrap(), vec![None]);
+    }
+
+    #[test]
+    fn test_errors_redact_tokens() {
+        let mut credentials = Credentials::default();
+        credentials.insert("localhost", "s3cret-token", CredentialSource::File);
+        let client = SecureHttpClient::new().unwrap().with_credentials(credentials);
+
+        // Nothing listens on port 9; the error must not carry the token
+        let err = client.download("http://localhost:9/s3cret-token/SKILL.md").unwrap_err();
+        let message = format!("{:#}", err);
+        assert!(message.contains("Failed to send HTTP request"));
+        assert!(!message.contains("s3cret-token"));
+    }
+
+    /// A raw HTTP response with the given extra headers and body
+    fn raw_response(headers: &str, body: &[u8]) -> Vec<u8> {
+        let mut response = format!("HTTP/1.1 200 OK\r\nContent-Type: text/plain\r\nConnection: close\r\n{}\r\n", headers).into_bytes();
+        response.extend_from_slice(body);
+        response
+    }
+
+    /// `body` as a chunked transfer encoding body, in 64 KiB chunks
+    fn chunked(body: &[u8]) -> Vec<u8> {
+        let mut encoded = Vec::new();
+        for chunk in body.chunks(64 * 1024) {
+            encoded.extend_from_slice(format!("{:x}\r\n", chunk.len()).as_bytes());
+            encoded.extend_from_slice(chunk);
+            encoded.extend_from_slice(b"\r\n");
+        }
+        encoded.extend_from_slice(b"0\r\n\r\n");
+        encoded
+    }
+
+    #[test]
+    fn test_download_streams_with_a_hard_cap() {
+        let client = SecureHttpClient::new().unwrap();
+        let oversized = vec![b'a'; 3 * MAX_DOWNLOAD_BYTES as usize];
+
+        // Chunked responses have no length to check up front
+        let small = mock_server::serve_raw(raw_response("Transfer-Encoding: chunked\r\n", &chunked(b"# Chunked skill")));
+        assert_eq!(client.download(&small).unwrap(), "# Chunked skill");
+
+        let large = mock_server::serve_raw(raw_response("Transfer-Encoding: chunked\r\n", &chunked(&oversized)));
+        let err = client.download(&large).unwrap_err();
+        assert!(err.to_string().contains("more than 1000000 bytes"), "{:#}", err);
+
+        // An understated Content-Length never lets more than it announced through
+        let understated = mock_server::serve_raw(raw_response("Content-Length: 100\r\n", &oversized));
+        assert_eq!(client.download(&understated).unwrap().len(), 100);
+
+        // An overstated one is rejected before the body is read
+        let overstated = mock_server::serve_raw(raw_response("Content-Length: 50000000\r\n", b"# Tiny"));
+        let err = client.download(&overstated).unwrap_err();
+        assert!(err.to_string().contains("50000000 bytes"), "{:#}", err);
+    }
+
+    #[test]
+    fn test_download_rejects_invalid_utf8() {
+        let client = SecureHttpClient::new().unwrap();
+        let body = b"# Skill \xff\xfe";
+        let server = mock_server::serve_raw(raw_response(&format!("Content-Length: {}\r\n", body.len()), body));
+
+        let err = client.download(&server).unwrap_err();
+        assert!(err.to_string().contains("not valid UTF-8"), "{:#}", err);
+    }
 }
@@ -0,0 +1,53 @@
+use anyhow::{Result, bail};
+use std::io::IsTerminal;
+
+/// How skillctl may interact with the user (from --yes / --non-interactive)
+#[derive(Debug, Clone, Copy)]
+pub struct Interaction {
+    /// Answer "yes" to every confirmation
+    pub assume_yes: bool,
+    /// Prompts may be shown (flag not set and attached to a terminal)
+    pub interactive: bool,
+}
+
+impl Interaction {
+    pub fn from_flags(yes: bool, non_interactive: bool) -> Self {
+        let has_tty = std::io::stdin().is_terminal() && std::io::stdout().is_terminal();
+        Self {
+            assume_yes: yes,
+            interactive: !non_interactive && has_tty,
+        }
+    }
+
+    /// Ask a yes/no question, honoring --yes and failing instead of blocking without a TTY
+    pub fn confirm(&self, prompt: &str) -> Result<bool> {
+        if self.assume_yes {
+            return Ok(true);
+        }
+        self.require_tty(&format!("Confirmation \"{}\"", prompt), "pass --yes to accept")?;
+        Ok(cliclack::confirm(prompt).interact()?)
+    }
+
+    /// Fail with a clear error if prompts are not allowed
+    pub fn require_tty(&self, action: &str, hint: &str) -> Result<()> {
+        if !self.interactive {
+            bail!("{} requires an interactive terminal ({})", action, hint);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_non_interactive_confirm() {
+        let yes = Interaction { assume_yes: true, interactive: false };
+        assert!(yes.confirm("Overwrite?").unwrap());
+
+        let no_tty = Interaction { assume_yes: false, interactive: false };
+        let err = no_tty.confirm("Overwrite?").unwrap_err();
+        assert!(err.to_string().contains("--yes"));
+    }
+}
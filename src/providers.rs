@@ -0,0 +1,774 @@
+use anyhow::{Result, Context, bail};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use url::Url;
+
+use crate::network::SecureHttpClient;
+
+const PAGE_SIZE: usize = 100;
+const MAX_PAGES: usize = 50;
+
+/// Software a trusted self-hosted forge runs (skills.json `trusted_hosts`)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Forge {
+    GitLab,
+    Gitea,
+    /// Bitbucket Data Center / Server
+    Bitbucket,
+}
+
+/// Self-hosted forges skills may be fetched from, by host name
+pub type TrustedHosts = BTreeMap<String, Forge>;
+
+/// A repository on a hosting service: where its raw files live and how to
+/// query the service's API
+pub trait SourceProvider {
+    /// Repository path on the host (`owner/repo`, `group/subgroup/repo`, `KEY/repo`)
+    fn project(&self) -> &str;
+
+    /// URL serving the raw content of `path` at a commit, branch or tag
+    fn raw_url(&self, reference: &str, path: &str) -> String;
+
+    /// Name of the repository's default branch
+    fn default_branch(&self, client: &SecureHttpClient) -> Result<String>;
+
+    /// Commit SHA that a branch, tag or abbreviated commit points to
+    fn resolve_commit(&self, client: &SecureHttpClient, reference: &str) -> Result<String>;
+
+    /// Every file path in the repository at `reference`
+    fn list_files(&self, client: &SecureHttpClient, reference: &str) -> Result<Vec<String>>;
+}
+
+/// github.com, through raw.githubusercontent.com and api.github.com
+pub struct GitHubProvider {
+    project: String,
+    api_base: String,
+    raw_base: String,
+}
+
+impl GitHubProvider {
+    pub fn new(project: impl Into<String>) -> Self {
+        Self {
+            project: project.into(),
+            api_base: "https://api.github.com".to_string(),
+            raw_base: "https://raw.githubusercontent.com".to_string(),
+        }
+    }
+
+    #[cfg(test)]
+    pub fn with_endpoints(project: &str, api_base: &str, raw_base: &str) -> Self {
+        Self { project: project.to_string(), api_base: api_base.to_string(), raw_base: raw_base.to_string() }
+    }
+}
+
+impl SourceProvider for GitHubProvider {
+    fn project(&self) -> &str {
+        &self.project
+    }
+
+    fn raw_url(&self, reference: &str, path: &str) -> String {
+        format!("{}/{}/{}/{}", self.raw_base, self.project, reference, path)
+    }
+
+    fn default_branch(&self, client: &SecureHttpClient) -> Result<String> {
+        #[derive(Deserialize)]
+        struct Repository {
+            default_branch: String,
+        }
+
+        let url = format!("{}/repos/{}", self.api_base, self.project);
+        let repository: Repository = client.get_json(&url)
+            .context("Failed to look up the default branch")?;
+        Ok(repository.default_branch)
+    }
+
+    fn resolve_commit(&self, client: &SecureHttpClient, reference: &str) -> Result<String> {
+        #[derive(Deserialize)]
+        struct Commit {
+            sha: String,
+        }
+
+        let url = format!("{}/repos/{}/commits/{}", self.api_base, self.project, encode(reference));
+        let commit: Commit = client.get_json(&url)
+            .with_context(|| format!("Failed to resolve '{}' to a commit", reference))?;
+        Ok(commit.sha)
+    }
+
+    fn list_files(&self, client: &SecureHttpClient, reference: &str) -> Result<Vec<String>> {
+        #[derive(Deserialize)]
+        struct TreeEntry {
+            path: String,
+            #[serde(rename = "type")]
+            kind: String,
+        }
+
+        #[derive(Deserialize)]
+        struct Tree {
+            tree: Vec<TreeEntry>,
+            #[serde(default)]
+            truncated: bool,
+        }
+
+        let url = format!("{}/repos/{}/git/trees/{}?recursive=1", self.api_base, self.project, encode(reference));
+        let tree: Tree = client.get_json(&url)?;
+
+        if tree.truncated {
+            bail!("Repository tree is too large to list in one request");
+        }
+
+        Ok(tree.tree.into_iter()
+            .filter(|e| e.kind == "blob")
+            .map(|e| e.path)
+            .collect())
+    }
+}
+
+/// gitlab.com or a self-hosted GitLab. Projects may sit in nested subgroups.
+pub struct GitLabProvider {
+    origin: String,
+    project: String,
+}
+
+impl GitLabProvider {
+    pub fn new(origin: impl Into<String>, project: impl Into<String>) -> Self {
+        Self { origin: origin.into(), project: project.into() }
+    }
+
+    /// Base of the API endpoints for this project (the path is URL-encoded as one id)
+    fn api(&self) -> String {
+        format!("{}/api/v4/projects/{}", self.origin, encode(&self.project))
+    }
+}
+
+impl SourceProvider for GitLabProvider {
+    fn project(&self) -> &str {
+        &self.project
+    }
+
+    fn raw_url(&self, reference: &str, path: &str) -> String {
+        format!("{}/{}/-/raw/{}/{}", self.origin, self.project, reference, path)
+    }
+
+    fn default_branch(&self, client: &SecureHttpClient) -> Result<String> {
+        #[derive(Deserialize)]
+        struct Project {
+            default_branch: Option<String>,
+        }
+
+        let project: Project = client.get_json(&self.api())
+            .context("Failed to look up the default branch")?;
+        project.default_branch.context("Repository is empty")
+    }
+
+    fn resolve_commit(&self, client: &SecureHttpClient, reference: &str) -> Result<String> {
+        #[derive(Deserialize)]
+        struct Commit {
+            id: String,
+        }
+
+        let url = format!("{}/repository/commits/{}", self.api(), encode(reference));
+        let commit: Commit = client.get_json(&url)
+            .with_context(|| format!("Failed to resolve '{}' to a commit", reference))?;
+        Ok(commit.id)
+    }
+
+    fn list_files(&self, client: &SecureHttpClient, reference: &str) -> Result<Vec<String>> {
+        #[derive(Deserialize)]
+        struct TreeEntry {
+            path: String,
+            #[serde(rename = "type")]
+            kind: String,
+        }
+
+        let mut files = Vec::new();
+        for page in 1..=MAX_PAGES {
+            let url = format!(
+                "{}/repository/tree?recursive=true&ref={}&per_page={}&page={}",
+                self.api(), encode(reference), PAGE_SIZE, page
+            );
+            let entries: Vec<TreeEntry> = client.get_json(&url)?;
+            let count = entries.len();
+
+            files.extend(entries.into_iter()
+                .filter(|e| e.kind == "blob")
+                .map(|e| e.path));
+
+            if count < PAGE_SIZE {
+                return Ok(files);
+            }
+        }
+
+        Err(too_many_pages(&self.project, reference))
+    }
+}
+
+/// A self-hosted Gitea (or Forgejo)
+pub struct GiteaProvider {
+    origin: String,
+    project: String,
+}
+
+impl SourceProvider for GiteaProvider {
+    fn project(&self) -> &str {
+        &self.project
+    }
+
+    fn raw_url(&self, reference: &str, path: &str) -> String {
+        // Gitea needs to know whether the ref is a commit or a branch
+        let kind = if is_commit_sha(reference) { "commit" } else { "branch" };
+        format!("{}/{}/raw/{}/{}/{}", self.origin, self.project, kind, reference, path)
+    }
+
+    fn default_branch(&self, client: &SecureHttpClient) -> Result<String> {
+        #[derive(Deserialize)]
+        struct Repository {
+            default_branch: String,
+        }
+
+        let url = format!("{}/api/v1/repos/{}", self.origin, self.project);
+        let repository: Repository = client.get_json(&url)
+            .context("Failed to look up the default branch")?;
+        Ok(repository.default_branch)
+    }
+
+    fn resolve_commit(&self, client: &SecureHttpClient, reference: &str) -> Result<String> {
+        #[derive(Deserialize)]
+        struct Commit {
+            sha: String,
+        }
+
+        let url = format!(
+            "{}/api/v1/repos/{}/commits?sha={}&limit=1&stat=false&files=false",
+            self.origin, self.project, encode(reference)
+        );
+        let commits: Vec<Commit> = client.get_json(&url)
+            .with_context(|| format!("Failed to resolve '{}' to a commit", reference))?;
+        commits.into_iter().next()
+            .map(|commit| commit.sha)
+            .with_context(|| format!("'{}' has no commits", reference))
+    }
+
+    fn list_files(&self, client: &SecureHttpClient, reference: &str) -> Result<Vec<String>> {
+        #[derive(Deserialize)]
+        struct TreeEntry {
+            path: String,
+            #[serde(rename = "type")]
+            kind: String,
+        }
+
+        #[derive(Deserialize)]
+        struct Tree {
+            #[serde(default)]
+            tree: Vec<TreeEntry>,
+            #[serde(default)]
+            truncated: bool,
+        }
+
+        let mut files = Vec::new();
+        for page in 1..=MAX_PAGES {
+            let url = format!(
+                "{}/api/v1/repos/{}/git/trees/{}?recursive=true&per_page={}&page={}",
+                self.origin, self.project, encode(reference), PAGE_SIZE, page
+            );
+            let tree: Tree = client.get_json(&url)?;
+
+            files.extend(tree.tree.into_iter()
+                .filter(|e| e.kind == "blob")
+                .map(|e| e.path));
+
+            if !tree.truncated {
+                return Ok(files);
+            }
+        }
+
+        Err(too_many_pages(&self.project, reference))
+    }
+}
+
+/// A self-hosted Bitbucket Data Center / Server, through its REST API 1.0
+pub struct BitbucketProvider {
+    origin: String,
+    /// `KEY/repo`
+    project: String,
+}
+
+impl BitbucketProvider {
+    fn key_and_repo(&self) -> (&str, &str) {
+        self.project.split_once('/').unwrap_or((&self.project, ""))
+    }
+
+    fn api(&self) -> String {
+        let (key, repo) = self.key_and_repo();
+        format!("{}/rest/api/1.0/projects/{}/repos/{}", self.origin, key, repo)
+    }
+}
+
+impl SourceProvider for BitbucketProvider {
+    fn project(&self) -> &str {
+        &self.project
+    }
+
+    fn raw_url(&self, reference: &str, path: &str) -> String {
+        let (key, repo) = self.key_and_repo();
+        format!("{}/projects/{}/repos/{}/raw/{}?at={}", self.origin, key, repo, path, encode(reference))
+    }
+
+    fn default_branch(&self, client: &SecureHttpClient) -> Result<String> {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Branch {
+            display_id: String,
+        }
+
+        let branch: Branch = client.get_json(&format!("{}/default-branch", self.api()))
+            .context("Failed to look up the default branch")?;
+        Ok(branch.display_id)
+    }
+
+    fn resolve_commit(&self, client: &SecureHttpClient, reference: &str) -> Result<String> {
+        #[derive(Deserialize)]
+        struct Commit {
+            id: String,
+        }
+
+        let url = format!("{}/commits/{}", self.api(), encode(reference));
+        let commit: Commit = client.get_json(&url)
+            .with_context(|| format!("Failed to resolve '{}' to a commit", reference))?;
+        Ok(commit.id)
+    }
+
+    fn list_files(&self, client: &SecureHttpClient, reference: &str) -> Result<Vec<String>> {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct FilesPage {
+            values: Vec<String>,
+            #[serde(default)]
+            is_last_page: bool,
+            next_page_start: Option<usize>,
+        }
+
+        let mut files = Vec::new();
+        let mut start = 0;
+        for _ in 0..MAX_PAGES {
+            let url = format!("{}/files?at={}&limit={}&start={}", self.api(), encode(reference), PAGE_SIZE, start);
+            let page: FilesPage = client.get_json(&url)?;
+            files.extend(page.values);
+
+            match page.next_page_start {
+                Some(next) if !page.is_last_page => start = next,
+                _ => return Ok(files),
+            }
+        }
+
+        Err(too_many_pages(&self.project, reference))
+    }
+}
+
+/// The provider serving a repository URL. Hosts other than github.com and
+/// gitlab.com must be in `trusted_hosts`.
+pub fn provider_for(repo_url: &str, trusted_hosts: &TrustedHosts) -> Result<Box<dyn SourceProvider>> {
+    let parsed = Url::parse(repo_url)
+        .context("Invalid repository URL")?;
+
+    let host = parsed.host_str().unwrap_or("");
+    let forge = match host {
+        "github.com" | "www.github.com" => None,
+        "gitlab.com" | "www.gitlab.com" => Some(Forge::GitLab),
+        _ => match trusted_hosts.get(&host.to_ascii_lowercase()) {
+            Some(forge) => Some(*forge),
+            None => bail!(
+                "Skill discovery is only supported for GitHub, GitLab and trusted hosts. Add {} to \"trusted_hosts\" in skills.json",
+                host
+            ),
+        },
+    };
+
+    let segments: Vec<&str> = parsed.path_segments()
+        .map(|s| s.filter(|seg| !seg.is_empty()).collect())
+        .unwrap_or_default();
+
+    let origin = match parsed.port() {
+        Some(port) => format!("{}://{}:{}", parsed.scheme(), host, port),
+        None => format!("{}://{}", parsed.scheme(), host),
+    };
+
+    let owner_and_repo = || -> Result<String> {
+        match segments.as_slice() {
+            [owner, repo, ..] => Ok(format!("{}/{}", owner, repo.trim_end_matches(".git"))),
+            _ => bail!("Repository URL must include owner and repository name"),
+        }
+    };
+
+    Ok(match forge {
+        None => Box::new(GitHubProvider::new(owner_and_repo()?)),
+        Some(Forge::GitLab) => {
+            // Ignore anything after GitLab's `/-/` separator (e.g. /-/tree/main)
+            let mut parts: Vec<String> = segments.iter()
+                .take_while(|seg| **seg != "-")
+                .map(|s| s.to_string())
+                .collect();
+            if parts.len() < 2 {
+                bail!("Repository URL must include owner and repository name");
+            }
+            if let Some(last) = parts.last_mut() {
+                *last = last.trim_end_matches(".git").to_string();
+            }
+            Box::new(GitLabProvider::new(origin, parts.join("/")))
+        }
+        Some(Forge::Gitea) => Box::new(GiteaProvider { origin, project: owner_and_repo()? }),
+        // Browse URLs (/projects/KEY/repos/repo) and clone URLs (/scm/key/repo.git)
+        Some(Forge::Bitbucket) => match segments.as_slice() {
+            ["projects", key, "repos", repo, ..] | ["scm", key, repo, ..] => Box::new(BitbucketProvider {
+                origin,
+                project: format!("{}/{}", key.to_ascii_uppercase(), repo.trim_end_matches(".git")),
+            }),
+            _ => bail!("Bitbucket URLs must look like /projects/KEY/repos/<repo>"),
+        },
+    })
+}
+
+/// Short identifier of a repository: `owner/repo` on GitHub, `host/project` elsewhere
+pub fn repo_id(repo_url: &str) -> String {
+    let host = Url::parse(repo_url).ok()
+        .and_then(|url| url.host_str().map(str::to_string));
+
+    match (provider_for(repo_url, &TrustedHosts::new()), host) {
+        (Ok(provider), Some(host)) if host == "github.com" || host == "www.github.com" => provider.project().to_string(),
+        (Ok(provider), Some(host)) => format!("{}/{}", host, provider.project()),
+        _ => repo_url.split("://").last().unwrap_or(repo_url)
+            .trim_end_matches('/')
+            .trim_end_matches(".git")
+            .to_string(),
+    }
+}
+
+/// A listing cut off at MAX_PAGES would install a partial skill directory, so it is an error
+fn too_many_pages(project: &str, reference: &str) -> anyhow::Error {
+    anyhow::anyhow!("Listing for {}@{} exceeds {} pages", project, reference, MAX_PAGES)
+}
+
+/// Percent-encode a value used as a single URL path segment or query value
+fn encode(value: &str) -> String {
+    url::form_urlencoded::byte_serialize(value.as_bytes()).collect()
+}
+
+fn is_commit_sha(reference: &str) -> bool {
+    reference.len() == 40 && reference.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock_server::serve_routes;
+
+    const SHA: &str = "0123456789abcdef0123456789abcdef01234567";
+
+    #[test]
+    fn test_provider_for() {
+        let none = TrustedHosts::new();
+
+        let github = provider_for("https://github.com/user/repo.git", &none).unwrap();
+        assert_eq!(github.project(), "user/repo");
+        assert_eq!(
+            github.raw_url("main", "skills/x/SKILL.md"),
+            "https://raw.githubusercontent.com/user/repo/main/skills/x/SKILL.md"
+        );
+
+        let gitlab = provider_for("https://gitlab.com/group/sub/repo/-/tree/main", &none).unwrap();
+        assert_eq!(gitlab.project(), "group/sub/repo");
+        assert_eq!(
+            gitlab.raw_url("main", "skills/x/SKILL.md"),
+            "https://gitlab.com/group/sub/repo/-/raw/main/skills/x/SKILL.md"
+        );
+
+        assert!(provider_for("https://github.com/user", &none).is_err());
+        assert!(provider_for("https://example.com/user/repo", &none).is_err());
+    }
+
+    #[test]
+    fn test_trusted_forge_urls() {
+        let trusted = TrustedHosts::from([
+            ("git.example.com".to_string(), Forge::GitLab),
+            ("gitea.example.com".to_string(), Forge::Gitea),
+            ("bitbucket.example.com".to_string(), Forge::Bitbucket),
+        ]);
+
+        let gitlab = provider_for("https://git.example.com:8443/team/ai/skills.git", &trusted).unwrap();
+        assert_eq!(gitlab.project(), "team/ai/skills");
+        assert_eq!(
+            gitlab.raw_url(SHA, "skills/x/SKILL.md"),
+            format!("https://git.example.com:8443/team/ai/skills/-/raw/{}/skills/x/SKILL.md", SHA)
+        );
+
+        let gitea = provider_for("https://gitea.example.com/team/skills/src/branch/main", &trusted).unwrap();
+        assert_eq!(gitea.project(), "team/skills");
+        assert_eq!(
+            gitea.raw_url("main", "skills/x/SKILL.md"),
+            "https://gitea.example.com/team/skills/raw/branch/main/skills/x/SKILL.md"
+        );
+        assert_eq!(
+            gitea.raw_url(SHA, "skills/x/SKILL.md"),
+            format!("https://gitea.example.com/team/skills/raw/commit/{}/skills/x/SKILL.md", SHA)
+        );
+
+        let bitbucket = provider_for("https://bitbucket.example.com/scm/ai/skills.git", &trusted).unwrap();
+        assert_eq!(bitbucket.project(), "AI/skills");
+        assert_eq!(
+            bitbucket.raw_url("release/1.0", "skills/x/SKILL.md"),
+            "https://bitbucket.example.com/projects/AI/repos/skills/raw/skills/x/SKILL.md?at=release%2F1.0"
+        );
+
+        // Only the listed host is trusted, not its subdomains
+        assert!(provider_for("https://other.git.example.com/team/skills", &trusted).is_err());
+    }
+
+    #[test]
+    fn test_repo_id() {
+        assert_eq!(repo_id("https://github.com/user/repo.git"), "user/repo");
+        assert_eq!(repo_id("https://gitlab.com/group/sub/repo"), "gitlab.com/group/sub/repo");
+        assert_eq!(repo_id("https://example.com/user/repo/"), "example.com/user/repo");
+    }
+
+    #[test]
+    fn test_github_provider() {
+        let base = serve_routes(vec![
+            ("/repos/user/repo".to_string(), "application/json", r#"{"default_branch": "trunk"}"#.to_string()),
+            ("/repos/user/repo/commits/v1.0".to_string(), "application/json", format!(r#"{{"sha": "{}"}}"#, SHA)),
+            (
+                format!("/repos/user/repo/git/trees/{}?recursive=1", SHA),
+                "application/json",
+                r#"{"tree": [
+                    {"path": "skills", "type": "tree"},
+                    {"path": "skills/demo/SKILL.md", "type": "blob"},
+                    {"path": "skills/demo/scripts/run.sh", "type": "blob"}
+                ], "truncated": false}"#.to_string(),
+            ),
+            (
+                format!("/raw/user/repo/{}/skills/demo/SKILL.md", SHA),
+                "text/plain; charset=utf-8",
+                "---\nname: demo\ndescription: Demo\n---\n# Demo\n".to_string(),
+            ),
+            (
+                format!("/raw/user/repo/{}/skills/demo/scripts/run.sh", SHA),
+                "text/plain; charset=utf-8",
+                "echo demo\n".to_string(),
+            ),
+        ]);
+
+        let client = SecureHttpClient::new().unwrap();
+        let provider = GitHubProvider::with_endpoints("user/repo", &base, &format!("{}/raw", base));
+
+        assert_eq!(provider.default_branch(&client).unwrap(), "trunk");
+        assert_eq!(provider.resolve_commit(&client, "v1.0").unwrap(), SHA);
+        assert!(provider.resolve_commit(&client, "missing").is_err());
+        assert_eq!(
+            provider.list_files(&client, SHA).unwrap(),
+            vec!["skills/demo/SKILL.md", "skills/demo/scripts/run.sh"]
+        );
+
+        let fetched = client.find_skill_in(&provider, "demo", None, Some(SHA)).unwrap();
+        assert_eq!(fetched.path, "skills/demo/SKILL.md");
+        assert_eq!(fetched.branch, SHA);
+
+        let files = client.fetch_skill_dir_in(&provider, SHA, &fetched.path).unwrap();
+        let paths: Vec<&str> = files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(paths, vec!["SKILL.md", "scripts/run.sh"]);
+    }
+
+    #[test]
+    fn test_skill_dir_failures_are_errors() {
+        let tree = |extra: &str| format!(r#"{{"tree": [
+            {{"path": "skills/demo/SKILL.md", "type": "blob"}},
+            {{"path": "skills/demo/{}", "type": "blob"}}
+        ], "truncated": false}}"#, extra);
+        let skill_md = || (
+            format!("/raw/user/repo/{}/skills/demo/SKILL.md", SHA),
+            "text/plain",
+            "---\ndescription: Demo\n---\n".to_string(),
+        );
+        let client = SecureHttpClient::new().unwrap();
+
+        // A binary file is refused by name, before anything is downloaded
+        let base = serve_routes(vec![
+            (format!("/repos/user/repo/git/trees/{}?recursive=1", SHA), "application/json", tree("assets/logo.png")),
+            skill_md(),
+        ]);
+        let provider = GitHubProvider::with_endpoints("user/repo", &base, &format!("{}/raw", base));
+        let err = client.fetch_skill_dir_in(&provider, SHA, "skills/demo/SKILL.md").unwrap_err();
+        assert!(err.to_string().contains("assets/logo.png"), "{:#}", err);
+
+        // A supporting file that cannot be downloaded fails the whole skill
+        let base = serve_routes(vec![
+            (format!("/repos/user/repo/git/trees/{}?recursive=1", SHA), "application/json", tree("scripts/run.sh")),
+            skill_md(),
+        ]);
+        let provider = GitHubProvider::with_endpoints("user/repo", &base, &format!("{}/raw", base));
+        let err = client.fetch_skill_dir_in(&provider, SHA, "skills/demo/SKILL.md").unwrap_err();
+        assert!(err.to_string().contains("scripts/run.sh"), "{:#}", err);
+    }
+
+    #[test]
+    fn test_gitlab_provider() {
+        // Subgroups make the project id `group%2Fsub%2Frepo`
+        let api = "/api/v4/projects/group%2Fsub%2Frepo";
+        let base = serve_routes(vec![
+            (api.to_string(), "application/json", r#"{"default_branch": "develop"}"#.to_string()),
+            (
+                format!("{}/repository/commits/feature%2Fnew", api),
+                "application/json",
+                format!(r#"{{"id": "{}"}}"#, SHA),
+            ),
+            (
+                format!("{}/repository/tree?recursive=true&ref=develop&per_page=100&page=1", api),
+                "application/json",
+                r#"[
+                    {"path": "skills/demo", "type": "tree"},
+                    {"path": "skills/demo/SKILL.md", "type": "blob"}
+                ]"#.to_string(),
+            ),
+            (
+                "/group/sub/repo/-/raw/develop/skills/demo/SKILL.md".to_string(),
+                "text/plain; charset=utf-8",
+                "---\nname: demo\ndescription: Demo\n---\n# Demo\n".to_string(),
+            ),
+        ]);
+
+        let client = SecureHttpClient::new().unwrap();
+        let provider = GitLabProvider::new(base.clone(), "group/sub/repo");
+
+        assert_eq!(provider.default_branch(&client).unwrap(), "develop");
+        assert_eq!(provider.resolve_commit(&client, "feature/new").unwrap(), SHA);
+        assert_eq!(provider.list_files(&client, "develop").unwrap(), vec!["skills/demo/SKILL.md"]);
+
+        // Without a ref, the default branch comes from the API instead of guessing main/master
+        let fetched = client.find_skill_in(&provider, "demo", None, None).unwrap();
+        assert_eq!(fetched.branch, "develop");
+        // Served only by the `/-/raw/develop/` route
+        assert_eq!(fetched.path, "skills/demo/SKILL.md");
+        assert!(fetched.content.contains("# Demo"));
+
+        let skills = client.list_skills_in(&provider, None, None).unwrap();
+        assert_eq!(skills.len(), 1);
+        assert_eq!(skills[0].name, "demo");
+        assert_eq!(skills[0].description.as_deref(), Some("Demo"));
+    }
+
+    #[test]
+    fn test_gitea_provider() {
+        let api = "/api/v1/repos/team/skills";
+        let tree_page = |project: &str, page: usize, body: &str| (
+            format!("/api/v1/repos/{}/git/trees/{}?recursive=true&per_page=100&page={}", project, SHA, page),
+            "application/json",
+            body.to_string(),
+        );
+        let mut routes = vec![
+            (api.to_string(), "application/json", r#"{"default_branch": "trunk"}"#.to_string()),
+            (
+                format!("{}/commits?sha=v1.0&limit=1&stat=false&files=false", api),
+                "application/json",
+                format!(r#"[{{"sha": "{}"}}]"#, SHA),
+            ),
+            tree_page("team/skills", 1, r#"{"tree": [
+                {"path": "skills/demo", "type": "tree"},
+                {"path": "skills/demo/SKILL.md", "type": "blob"}
+            ], "truncated": true}"#),
+            tree_page("team/skills", 2, r#"{"tree": [
+                {"path": "skills/demo/scripts/run.sh", "type": "blob"}
+            ], "truncated": false}"#),
+            (
+                format!("/team/skills/raw/commit/{}/skills/demo/SKILL.md", SHA),
+                "text/plain; charset=utf-8",
+                "---\nname: demo\ndescription: Demo\n---\n# Demo\n".to_string(),
+            ),
+            (
+                format!("/team/skills/raw/commit/{}/skills/demo/scripts/run.sh", SHA),
+                "text/plain; charset=utf-8",
+                "echo demo\n".to_string(),
+            ),
+        ];
+        // A tree still truncated after MAX_PAGES pages
+        routes.extend((1..=MAX_PAGES).map(|page| tree_page(
+            "team/huge",
+            page,
+            &format!(r#"{{"tree": [{{"path": "file{}.md", "type": "blob"}}], "truncated": true}}"#, page),
+        )));
+        let base = serve_routes(routes);
+
+        let client = SecureHttpClient::new().unwrap();
+        let provider = GiteaProvider { origin: base.clone(), project: "team/skills".to_string() };
+
+        assert_eq!(provider.default_branch(&client).unwrap(), "trunk");
+        assert_eq!(provider.resolve_commit(&client, "v1.0").unwrap(), SHA);
+        assert!(provider.resolve_commit(&client, "missing").is_err());
+        assert_eq!(
+            provider.list_files(&client, SHA).unwrap(),
+            vec!["skills/demo/SKILL.md", "skills/demo/scripts/run.sh"]
+        );
+
+        let files = client.fetch_skill_dir_in(&provider, SHA, "skills/demo/SKILL.md").unwrap();
+        let paths: Vec<&str> = files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(paths, vec!["SKILL.md", "scripts/run.sh"]);
+
+        let huge = GiteaProvider { origin: base, project: "team/huge".to_string() };
+        let err = huge.list_files(&client, SHA).unwrap_err();
+        assert_eq!(err.to_string(), format!("Listing for team/huge@{} exceeds {} pages", SHA, MAX_PAGES));
+    }
+
+    #[test]
+    fn test_bitbucket_provider() {
+        let api = "/rest/api/1.0/projects/AI/repos/skills";
+        let files_page = |repo: &str, start: usize, body: String| (
+            format!("/rest/api/1.0/projects/AI/repos/{}/files?at={}&limit=100&start={}", repo, SHA, start),
+            "application/json",
+            body,
+        );
+        let mut routes = vec![
+            (
+                format!("{}/default-branch", api),
+                "application/json",
+                r#"{"id": "refs/heads/master", "displayId": "master"}"#.to_string(),
+            ),
+            (format!("{}/commits/release%2F1.0", api), "application/json", format!(r#"{{"id": "{}"}}"#, SHA)),
+            files_page("skills", 0, r#"{"values": ["README.md", "skills/demo/SKILL.md"],
+                "isLastPage": false, "nextPageStart": 2}"#.to_string()),
+            files_page("skills", 2, r#"{"values": ["skills/demo/scripts/run.sh"], "isLastPage": true}"#.to_string()),
+            (
+                format!("/projects/AI/repos/skills/raw/skills/demo/SKILL.md?at={}", SHA),
+                "text/plain; charset=utf-8",
+                "---\nname: demo\ndescription: Demo\n---\n# Demo\n".to_string(),
+            ),
+            (
+                format!("/projects/AI/repos/skills/raw/skills/demo/scripts/run.sh?at={}", SHA),
+                "text/plain; charset=utf-8",
+                "echo demo\n".to_string(),
+            ),
+        ];
+        // More pages than MAX_PAGES
+        routes.extend((0..MAX_PAGES).map(|start| files_page(
+            "huge",
+            start,
+            format!(r#"{{"values": ["file{}.md"], "isLastPage": false, "nextPageStart": {}}}"#, start, start + 1),
+        )));
+        let base = serve_routes(routes);
+
+        let client = SecureHttpClient::new().unwrap();
+        let provider = BitbucketProvider { origin: base.clone(), project: "AI/skills".to_string() };
+
+        assert_eq!(provider.default_branch(&client).unwrap(), "master");
+        assert_eq!(provider.resolve_commit(&client, "release/1.0").unwrap(), SHA);
+        assert!(provider.resolve_commit(&client, "missing").is_err());
+        assert_eq!(
+            provider.list_files(&client, SHA).unwrap(),
+            vec!["README.md", "skills/demo/SKILL.md", "skills/demo/scripts/run.sh"]
+        );
+
+        let files = client.fetch_skill_dir_in(&provider, SHA, "skills/demo/SKILL.md").unwrap();
+        let paths: Vec<&str> = files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(paths, vec!["SKILL.md", "scripts/run.sh"]);
+
+        let huge = BitbucketProvider { origin: base, project: "AI/huge".to_string() };
+        let err = huge.list_files(&client, SHA).unwrap_err();
+        assert_eq!(err.to_string(), format!("Listing for AI/huge@{} exceeds {} pages", SHA, MAX_PAGES));
+    }
+}
@@ -0,0 +1,105 @@
+use std::fmt;
+use std::path::{Path, PathBuf};
+use anyhow::{Result, Context};
+use crate::adapters::EditorAdapter;
+use crate::editors::EditorType;
+use crate::lockfile::LOCKFILE_PATH;
+
+const CONFIG_FILE: &str = "skills.json";
+
+/// Where skillctl keeps its configuration and which editor files it writes
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum Scope {
+    /// `./skills.json`, `.skillctl/store` and the project's editor files
+    #[default]
+    Project,
+    /// User-level installs (`--global`): configuration and store under
+    /// `config_dir`, editor files under `home`
+    Global { config_dir: PathBuf, home: PathBuf },
+}
+
+impl Scope {
+    /// The user-level scope, rooted at `$XDG_CONFIG_HOME/skillctl` or `~/.config/skillctl`
+    pub fn global() -> Result<Self> {
+        let home = dirs::home_dir()
+            .context("Could not determine the home directory for --global")?;
+
+        let config_home = std::env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .filter(|dir| dir.is_absolute())
+            .unwrap_or_else(|| home.join(".config"));
+
+        Ok(Scope::Global { config_dir: config_home.join("skillctl"), home })
+    }
+
+    pub fn is_global(&self) -> bool {
+        matches!(self, Scope::Global { .. })
+    }
+
+    fn root(&self) -> &Path {
+        match self {
+            Scope::Project => Path::new(""),
+            Scope::Global { config_dir, .. } => config_dir,
+        }
+    }
+
+    pub fn config_path(&self) -> PathBuf {
+        self.root().join(CONFIG_FILE)
+    }
+
+    pub fn lockfile_path(&self) -> PathBuf {
+        self.root().join(LOCKFILE_PATH)
+    }
+
+    pub fn default_store_path(&self) -> String {
+        match self {
+            Scope::Project => ".skillctl/store".to_string(),
+            Scope::Global { config_dir, .. } => config_dir.join("store").to_string_lossy().to_string(),
+        }
+    }
+
+    /// The adapter writing `editor`'s files in this scope. Editors without a
+    /// user-level location have no global adapter.
+    pub fn adapter(&self, editor: &EditorType) -> Option<Box<dyn EditorAdapter>> {
+        match self {
+            Scope::Project => Some(editor.adapter()),
+            Scope::Global { home, .. } => editor.user_adapter(home),
+        }
+    }
+}
+
+impl fmt::Display for Scope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Scope::Project => write!(f, "project"),
+            Scope::Global { .. } => write!(f, "global"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scope_paths() {
+        let project = Scope::Project;
+        assert_eq!(project.config_path(), PathBuf::from("skills.json"));
+        assert_eq!(project.lockfile_path(), PathBuf::from("skills.lock"));
+        assert_eq!(project.default_store_path(), ".skillctl/store");
+
+        let global = Scope::Global {
+            config_dir: PathBuf::from("/home/dev/.config/skillctl"),
+            home: PathBuf::from("/home/dev"),
+        };
+        assert_eq!(global.config_path(), PathBuf::from("/home/dev/.config/skillctl/skills.json"));
+        assert_eq!(global.default_store_path(), "/home/dev/.config/skillctl/store");
+
+        // User-level editor locations live in the home directory
+        let claude = global.adapter(&EditorType::ClaudeCode).unwrap();
+        assert_eq!(claude.skills_dir(), PathBuf::from("/home/dev/.claude/skills"));
+        assert_eq!(claude.config_file(), PathBuf::from("/home/dev/.claude/CLAUDE.md"));
+        assert_eq!(project.adapter(&EditorType::ClaudeCode).unwrap().config_file(), PathBuf::from("CLAUDE.md"));
+        assert!(global.adapter(&EditorType::Cline).is_none());
+    }
+}
@@ -1,24 +1,79 @@
-use anyhow::{Result, Context};
+use anyhow::{Result, Context, bail};
+use std::collections::{BTreeMap, BTreeSet};
 use std::path::{Path, PathBuf};
 use std::fs;
 use sha2::{Sha256, Digest};
 use chrono::Utc;
-use crate::security::{validate_skill_name, validate_path_in_store};
-use crate::editors::{SkillEntry, SkillConfig, save_config};
+use crate::security::{
+    validate_skill_name, validate_path_in_store, validate_skill_file_path, validate_skill_content, is_binary_file_name,
+    MAX_SKILL_FILES, MAX_SKILL_TOTAL_BYTES,
+};
+use crate::editors::{SkillEntry, SkillConfig, save_config, symlink_dir};
 
+/// Directory of content-addressed skill blobs inside the shared store
+const BLOB_DIR: &str = "sha256";
+
+/// Stores that link into the shared store, consulted by `store gc`
+const STORES_FILE: &str = "stores.json";
+
+/// A project's (or the global) skill store: one entry per skill name, each a
+/// link to the skill's content in the shared [`BlobStore`], or to a local skill
+/// directory added with `add --dev`
 pub struct SkillStore {
     base_path: PathBuf,
+    blobs: BlobStore,
+    /// Local directories that `--dev` skills link to
+    linked: Vec<PathBuf>,
+}
+
+/// Content-addressed skill directories shared by every store on the machine.
+/// Each skill version is written once under `sha256/<hash>/`, so projects using
+/// the same skill share one copy and same-named skills never overwrite each other.
+pub struct BlobStore {
+    root: PathBuf,
+}
+
+/// Outcome of [`BlobStore::gc`]
+#[derive(Debug, Default, serde::Serialize)]
+pub struct GcReport {
+    pub removed: Vec<String>,
+    pub kept: usize,
+    pub freed_bytes: u64,
+}
+
+/// One file of a skill directory, with its path relative to the directory
+#[derive(Debug, Clone, PartialEq)]
+pub struct SkillFile {
+    pub path: String,
+    pub content: String,
+}
+
+impl SkillFile {
+    pub fn new(path: impl Into<String>, content: impl Into<String>) -> Self {
+        Self { path: path.into(), content: content.into() }
+    }
 }
 
 impl SkillStore {
+    /// Open a store linking into the user's shared blob store
     pub fn new(base_path: impl AsRef<Path>) -> Result<Self> {
+        Self::with_blobs(base_path, BlobStore::open_default()?)
+    }
+
+    pub fn with_blobs(base_path: impl AsRef<Path>, blobs: BlobStore) -> Result<Self> {
         let base_path = base_path.as_ref().to_path_buf();
         
         // Create store directory if it doesn't exist
         fs::create_dir_all(&base_path)
             .context("Failed to create skill store directory")?;
 
-        Ok(Self { base_path })
+        Ok(Self { base_path, blobs, linked: Vec::new() })
+    }
+
+    /// Allow store entries that link to these local skill directories (`add --dev`)
+    pub fn with_linked_sources(mut self, dirs: impl IntoIterator<Item = PathBuf>) -> Self {
+        self.linked.extend(dirs);
+        self
     }
 
     /// Calculate SHA256 hash of content
@@ -28,45 +83,149 @@ impl SkillStore {
         hex::encode(hasher.finalize())
     }
 
-    /// Install a skill to the store
+    /// Calculate the aggregate hash of a skill directory from its per-file hashes.
+    /// A skill made only of SKILL.md hashes to the SKILL.md hash itself, so entries
+    /// recorded before directory installs remain valid.
+    pub fn calculate_tree_hash(file_hashes: &BTreeMap<String, String>) -> String {
+        if file_hashes.len() == 1 {
+            if let Some(hash) = file_hashes.get("SKILL.md") {
+                return hash.clone();
+            }
+        }
+
+        let mut hasher = Sha256::new();
+        for (path, hash) in file_hashes {
+            hasher.update(path);
+            hasher.update([0u8]);
+            hasher.update(hash);
+            hasher.update(b"\n");
+        }
+        hex::encode(hasher.finalize())
+    }
+
+    /// Per-file SHA256 hashes of a set of skill files
+    pub fn file_hashes(files: &[SkillFile]) -> BTreeMap<String, String> {
+        files.iter()
+            .map(|f| (f.path.clone(), Self::calculate_hash(&f.content)))
+            .collect()
+    }
+
+    /// Aggregate hash of a set of skill files
+    pub fn hash_files(files: &[SkillFile]) -> String {
+        Self::calculate_tree_hash(&Self::file_hashes(files))
+    }
+
+    /// Install a single-file skill to the store (shorthand for tests)
+    #[cfg(test)]
     pub fn install_skill(
         &self,
         skill_name: &str,
         content: &str,
         source_url: &str,
     ) -> Result<SkillEntry> {
-        // Validate skill name (security check)
-        validate_skill_name(skill_name)?;
+        self.install_skill_files(skill_name, &[SkillFile::new("SKILL.md", content)], source_url)
+    }
+
+    /// Install a skill directory (SKILL.md plus supporting files) to the store
+    pub fn install_skill_files(
+        &self,
+        skill_name: &str,
+        files: &[SkillFile],
+        source_url: &str,
+    ) -> Result<SkillEntry> {
+        validate_files(skill_name, files)?;
+
+        let skill_dir = self.link_files(skill_name, files)?;
+        Ok(new_entry(&skill_dir, files, source_url))
+    }
 
-        // Calculate hash
-        let hash = Self::calculate_hash(content);
+    /// Link the skill's name to a local skill directory instead of copying it
+    /// (`add --dev`), so edits to the directory take effect immediately. Its
+    /// current files go through the same checks as an install.
+    pub fn link_local_skill(
+        &self,
+        skill_name: &str,
+        source_dir: &Path,
+        source_url: &str,
+    ) -> Result<SkillEntry> {
+        let files = crate::local::read_local_skill(source_dir)?;
+        validate_files(skill_name, &files)?;
 
-        // Create skill directory
+        let source_dir = source_dir.canonicalize()
+            .context("Failed to resolve local skill directory")?;
         let skill_dir = self.base_path.join(skill_name);
-        
-        // Validate path is within store (security check)
-        validate_path_in_store(&self.base_path, &skill_dir)?;
-
-        fs::create_dir_all(&skill_dir)
-            .context("Failed to create skill directory")?;
-
-        // Write SKILL.md file
-        let skill_file = skill_dir.join("SKILL.md");
-        fs::write(&skill_file, content)
-            .context("Failed to write SKILL.md file")?;
-
-        // Create entry
-        let entry = SkillEntry {
-            url: source_url.to_string(),
-            local_path: skill_file.to_string_lossy().to_string(),
-            hash,
-            last_updated: Utc::now().to_rfc3339(),
-        };
+        self.validate_in_store(&skill_dir)?;
+        unlink_skill_dir(&skill_dir)?;
+
+        symlink_dir(&source_dir, &skill_dir)
+            .context("Failed to link local skill (--dev needs symlink support)")?;
+        validate_path_in_store(&source_dir, &skill_dir)?;
+        self.blobs.register(&self.base_path)?;
 
+        let mut entry = new_entry(&skill_dir, &files, source_url);
+        entry.dev = true;
         Ok(entry)
     }
 
-    /// Check if a skill exists and verify its integrity
+    /// Check that a `--dev` skill still links to its local directory
+    pub fn verify_link(&self, skill_name: &str, source_dir: &Path) -> Result<bool> {
+        validate_skill_name(skill_name)?;
+
+        let skill_dir = self.base_path.join(skill_name);
+        let (Ok(target), Ok(source)) = (skill_dir.canonicalize(), source_dir.canonicalize()) else {
+            return Ok(false);
+        };
+        Ok(target == source && skill_dir.join("SKILL.md").is_file())
+    }
+
+    /// Point the skill's name at the blob holding `files`, replacing any previous version
+    fn link_files(&self, skill_name: &str, files: &[SkillFile]) -> Result<PathBuf> {
+        let (_, blob) = self.blobs.put(files)?;
+
+        let skill_dir = self.base_path.join(skill_name);
+        self.validate_in_store(&skill_dir)?;
+        unlink_skill_dir(&skill_dir)?;
+
+        if symlink_dir(&blob, &skill_dir).is_err() {
+            // Without symlink support the store keeps its own copy
+            write_files(&skill_dir, files)?;
+        }
+        self.validate_in_store(&skill_dir)?;
+        self.blobs.register(&self.base_path)?;
+
+        Ok(skill_dir)
+    }
+
+    /// Move a skill kept as a plain directory (stores written before the shared
+    /// store existed) into the shared store. Returns whether anything changed.
+    pub fn share_skill(&self, skill_name: &str) -> Result<bool> {
+        validate_skill_name(skill_name)?;
+
+        let skill_dir = self.base_path.join(skill_name);
+        match fs::symlink_metadata(&skill_dir) {
+            Ok(meta) if meta.is_dir() => {},
+            _ => return Ok(false),
+        }
+
+        let files = self.read_skill_files(skill_name)?;
+        self.link_files(skill_name, &files)?;
+        Ok(fs::symlink_metadata(&skill_dir)?.file_type().is_symlink())
+    }
+
+    /// Read every file of an installed skill
+    pub fn read_skill_files(&self, skill_name: &str) -> Result<Vec<SkillFile>> {
+        validate_skill_name(skill_name)?;
+
+        let skill_dir = self.base_path.join(skill_name);
+        let mut files = Vec::new();
+        if skill_dir.exists() {
+            collect_files(&skill_dir, &skill_dir, &mut files)?;
+        }
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(files)
+    }
+
+    /// Check if a skill exists and verify the integrity of all its files
     pub fn verify_skill(&self, skill_name: &str, expected_hash: &str) -> Result<bool> {
         validate_skill_name(skill_name)?;
 
@@ -76,10 +235,13 @@ impl SkillStore {
             return Ok(false);
         }
 
-        let content = fs::read_to_string(&skill_file)
-            .context("Failed to read skill file")?;
+        // Files that are not valid UTF-8 cannot have been written by skillctl
+        let files = match self.read_skill_files(skill_name) {
+            Ok(files) => files,
+            Err(_) => return Ok(false),
+        };
 
-        let actual_hash = Self::calculate_hash(&content);
+        let actual_hash = Self::hash_files(&files);
         
         Ok(actual_hash == expected_hash)
     }
@@ -91,14 +253,29 @@ impl SkillStore {
         let skill_dir = self.base_path.join(skill_name);
         
         // Validate path is within store (security check)
-        validate_path_in_store(&self.base_path, &skill_dir)?;
+        self.validate_in_store(&skill_dir)?;
 
-        if skill_dir.exists() {
-            fs::remove_dir_all(&skill_dir)
-                .context("Failed to remove skill directory")?;
+        // The blob stays in the shared store until `store gc`
+        unlink_skill_dir(&skill_dir)
+    }
+
+    /// List the skill directories present in the store
+    pub fn list_skills(&self) -> Result<Vec<String>> {
+        let mut names = Vec::new();
+
+        for dir_entry in fs::read_dir(&self.base_path).context("Failed to read skill store")? {
+            let dir_entry = dir_entry?;
+            let file_type = dir_entry.file_type()?;
+            if !file_type.is_dir() && !file_type.is_symlink() {
+                continue;
+            }
+            if let Some(name) = dir_entry.file_name().to_str() {
+                names.push(name.to_string());
+            }
         }
 
-        Ok(())
+        names.sort();
+        Ok(names)
     }
 
     /// Get the path to a skill file
@@ -108,11 +285,313 @@ impl SkillStore {
         let skill_file = self.base_path.join(skill_name).join("SKILL.md");
         
         // Validate path is within store (security check)
-        validate_path_in_store(&self.base_path, &skill_file)?;
+        self.validate_in_store(&skill_file)?;
 
         Ok(skill_file)
     }
 
+    /// Skill paths resolve inside this store, into the shared blobs or into a
+    /// linked local skill directory
+    fn validate_in_store(&self, path: &Path) -> Result<PathBuf> {
+        validate_path_in_store(&self.base_path, path)
+            .or_else(|e| self.linked.iter()
+                .find_map(|dir| validate_path_in_store(dir, path).ok())
+                .ok_or(e))
+            .or_else(|_| validate_path_in_store(&self.blobs.blobs_dir(), path))
+    }
+}
+
+impl BlobStore {
+    /// The shared store under the user's cache directory (`~/.cache/skillctl` on Linux)
+    pub fn open_default() -> Result<Self> {
+        Self::open(default_root()?)
+    }
+
+    pub fn open(root: impl AsRef<Path>) -> Result<Self> {
+        let root = root.as_ref().to_path_buf();
+        fs::create_dir_all(root.join(BLOB_DIR))
+            .context("Failed to create shared skill store")?;
+        Ok(Self { root })
+    }
+
+    fn blobs_dir(&self) -> PathBuf {
+        self.root.join(BLOB_DIR)
+    }
+
+    /// Directory holding the skill content with the given tree hash
+    pub fn blob_path(&self, hash: &str) -> Result<PathBuf> {
+        if hash.len() != 64 || !hash.bytes().all(|b| b.is_ascii_hexdigit()) {
+            bail!("Invalid skill hash: {}", hash);
+        }
+        Ok(self.blobs_dir().join(hash.to_ascii_lowercase()))
+    }
+
+    /// Write `files` as a blob, reusing an intact existing blob with the same hash.
+    /// Returns the tree hash and the blob directory.
+    fn put(&self, files: &[SkillFile]) -> Result<(String, PathBuf)> {
+        let hash = SkillStore::hash_files(files);
+        let blob = self.blob_path(&hash)?;
+        if hash_dir(&blob).as_deref() == Some(hash.as_str()) {
+            return Ok((hash, blob));
+        }
+
+        // Build the blob aside and move it into place so readers never see a partial one
+        let staging = self.root.join(format!("tmp-{}", uuid::Uuid::new_v4()));
+        if let Err(err) = write_files(&staging, files) {
+            let _ = fs::remove_dir_all(&staging);
+            return Err(err);
+        }
+        if blob.exists() {
+            fs::remove_dir_all(&blob)
+                .context("Failed to replace damaged skill in the shared store")?;
+        }
+        if let Err(err) = fs::rename(&staging, &blob) {
+            let _ = fs::remove_dir_all(&staging);
+            // Another skillctl may have stored the same content meanwhile
+            if hash_dir(&blob).as_deref() != Some(hash.as_str()) {
+                return Err(err).context("Failed to add skill to the shared store");
+            }
+        }
+
+        Ok((hash, blob))
+    }
+
+    /// Whether `path` lies in a skill store on this machine: inside a shared blob,
+    /// or an entry of a store registered here (which covers `--dev` links and
+    /// stores that had to copy instead of link)
+    pub fn owns(&self, path: &Path) -> bool {
+        if let (Ok(blobs_dir), Ok(target)) = (self.blobs_dir().canonicalize(), path.canonicalize()) {
+            if target.starts_with(&blobs_dir) {
+                return true;
+            }
+        }
+
+        let Some(store) = path.parent().and_then(|parent| parent.canonicalize().ok()) else {
+            return false;
+        };
+        self.registered_stores()
+            .map(|stores| stores.contains(&store))
+            .unwrap_or(false)
+    }
+
+    /// Remember a store linking into this one so `gc` keeps its blobs
+    pub(crate) fn register(&self, store: &Path) -> Result<()> {
+        let store = store.canonicalize()
+            .context("Failed to resolve skill store directory")?;
+        let mut stores = self.registered_stores()?;
+        if stores.insert(store) {
+            self.save_stores(&stores)?;
+        }
+        Ok(())
+    }
+
+    fn registered_stores(&self) -> Result<BTreeSet<PathBuf>> {
+        let path = self.root.join(STORES_FILE);
+        if !path.exists() {
+            return Ok(BTreeSet::new());
+        }
+        let content = fs::read_to_string(&path)
+            .context("Failed to read shared store registry")?;
+        serde_json::from_str(&content)
+            .context("Failed to parse shared store registry")
+    }
+
+    fn save_stores(&self, stores: &BTreeSet<PathBuf>) -> Result<()> {
+        let content = serde_json::to_string_pretty(stores)?;
+        fs::write(self.root.join(STORES_FILE), content)
+            .context("Failed to write shared store registry")
+    }
+
+    /// Hashes of the blobs linked from registered stores. Stores that no longer
+    /// exist are dropped from the registry.
+    fn referenced_hashes(&self) -> Result<BTreeSet<String>> {
+        let blobs_dir = self.blobs_dir().canonicalize()
+            .context("Failed to resolve shared skill store")?;
+        let stores = self.registered_stores()?;
+        let live: BTreeSet<PathBuf> = stores.iter().filter(|s| s.is_dir()).cloned().collect();
+
+        let mut referenced = BTreeSet::new();
+        for store in &live {
+            for dir_entry in fs::read_dir(store).context("Failed to read skill store")? {
+                let Ok(target) = dir_entry?.path().canonicalize() else {
+                    continue;
+                };
+                if target.parent() == Some(blobs_dir.as_path()) {
+                    if let Some(hash) = target.file_name().and_then(|n| n.to_str()) {
+                        referenced.insert(hash.to_string());
+                    }
+                }
+            }
+        }
+
+        if live.len() != stores.len() {
+            self.save_stores(&live)?;
+        }
+        Ok(referenced)
+    }
+
+    /// Remove every blob that no registered store links to
+    pub fn gc(&self) -> Result<GcReport> {
+        let referenced = self.referenced_hashes()?;
+        let mut report = GcReport::default();
+
+        let mut blobs = Vec::new();
+        for dir_entry in fs::read_dir(self.blobs_dir()).context("Failed to read shared skill store")? {
+            let dir_entry = dir_entry?;
+            if let Some(hash) = dir_entry.file_name().to_str() {
+                blobs.push((hash.to_string(), dir_entry.path()));
+            }
+        }
+        blobs.sort();
+
+        for (hash, path) in blobs {
+            if referenced.contains(&hash) {
+                report.kept += 1;
+                continue;
+            }
+            report.freed_bytes += dir_size(&path);
+            fs::remove_dir_all(&path)
+                .with_context(|| format!("Failed to remove blob {}", hash))?;
+            report.removed.push(hash);
+        }
+
+        Ok(report)
+    }
+}
+
+/// Check a skill's files before they are installed: name, SKILL.md, limits and content
+fn validate_files(skill_name: &str, files: &[SkillFile]) -> Result<()> {
+    // Validate skill name (security check)
+    validate_skill_name(skill_name)?;
+
+    if !files.iter().any(|f| f.path == "SKILL.md") {
+        bail!("Skill '{}' has no SKILL.md", skill_name);
+    }
+    if files.len() > MAX_SKILL_FILES {
+        bail!("Skill has too many files ({} > {})", files.len(), MAX_SKILL_FILES);
+    }
+    let total_size: usize = files.iter().map(|f| f.content.len()).sum();
+    if total_size > MAX_SKILL_TOTAL_BYTES {
+        bail!("Skill is too large ({} bytes, max {})", total_size, MAX_SKILL_TOTAL_BYTES);
+    }
+    for file in files {
+        validate_skill_file_path(&file.path)?;
+        validate_skill_content(&file.content)
+            .with_context(|| format!("Invalid skill file {}", file.path))?;
+    }
+    Ok(())
+}
+
+/// The skills.json entry for `files` installed at `skill_dir`
+fn new_entry(skill_dir: &Path, files: &[SkillFile], source_url: &str) -> SkillEntry {
+    let file_hashes = SkillStore::file_hashes(files);
+    SkillEntry {
+        url: source_url.to_string(),
+        local_path: skill_dir.join("SKILL.md").to_string_lossy().to_string(),
+        hash: SkillStore::calculate_tree_hash(&file_hashes),
+        last_updated: Utc::now().to_rfc3339(),
+        path: None,
+        resolved_ref: None,
+        reference: None,
+        files: if file_hashes.len() > 1 { file_hashes } else { BTreeMap::new() },
+        skill: None,
+        dev: false,
+    }
+}
+
+#[cfg(not(test))]
+fn default_root() -> Result<PathBuf> {
+    let cache_dir = dirs::cache_dir()
+        .context("Could not determine the cache directory for the shared skill store")?;
+    Ok(cache_dir.join("skillctl"))
+}
+
+/// Tests never touch the developer's real cache
+#[cfg(test)]
+fn default_root() -> Result<PathBuf> {
+    Ok(std::env::temp_dir().join(format!("skillctl-test-cache-{}", std::process::id())))
+}
+
+/// Write skill files under `dir`, which must not exist yet
+fn write_files(dir: &Path, files: &[SkillFile]) -> Result<()> {
+    fs::create_dir_all(dir)
+        .context("Failed to create skill directory")?;
+
+    for file in files {
+        let target = dir.join(&file.path);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)
+                .context("Failed to create skill subdirectory")?;
+        }
+
+        // Validate path is within the skill directory (security check)
+        validate_path_in_store(dir, &target)?;
+
+        fs::write(&target, &file.content)
+            .with_context(|| format!("Failed to write {}", file.path))?;
+    }
+    Ok(())
+}
+
+/// Tree hash of the files under `dir`, if it can be read
+fn hash_dir(dir: &Path) -> Option<String> {
+    if !dir.is_dir() {
+        return None;
+    }
+    let mut files = Vec::new();
+    collect_files(dir, dir, &mut files).ok()?;
+    Some(SkillStore::hash_files(&files))
+}
+
+fn dir_size(dir: &Path) -> u64 {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return 0;
+    };
+    entries.flatten()
+        .map(|entry| match entry.file_type() {
+            Ok(t) if t.is_dir() => dir_size(&entry.path()),
+            _ => entry.metadata().map(|m| m.len()).unwrap_or(0),
+        })
+        .sum()
+}
+
+/// Remove a store entry: the link to its blob, or a plain copy
+fn unlink_skill_dir(skill_dir: &Path) -> Result<()> {
+    match fs::symlink_metadata(skill_dir) {
+        Ok(meta) if meta.file_type().is_symlink() => fs::remove_file(skill_dir)
+            .or_else(|_| fs::remove_dir(skill_dir))
+            .context("Failed to unlink skill directory"),
+        Ok(_) => fs::remove_dir_all(skill_dir)
+            .context("Failed to remove skill directory"),
+        Err(_) => Ok(()),
+    }
+}
+
+/// Recursively read all files under `dir`, with paths relative to `root`
+pub(crate) fn collect_files(root: &Path, dir: &Path, files: &mut Vec<SkillFile>) -> Result<()> {
+    for dir_entry in fs::read_dir(dir).context("Failed to read skill directory")? {
+        let dir_entry = dir_entry?;
+        let path = dir_entry.path();
+        let file_type = dir_entry.file_type()?;
+
+        if file_type.is_dir() {
+            collect_files(root, &path, files)?;
+        } else if file_type.is_file() {
+            let relative = path.strip_prefix(root)
+                .context("Skill file outside its directory")?
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy().to_string())
+                .collect::<Vec<_>>()
+                .join("/");
+            if is_binary_file_name(&relative) {
+                bail!("Binary skill files are not supported (skills may only contain text): {}", relative);
+            }
+            let content = fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read {}", relative))?;
+            files.push(SkillFile::new(relative, content));
+        }
+    }
+    Ok(())
 }
 
 /// Update a skill in the configuration and store
@@ -141,6 +620,12 @@ mod tests {
     use super::*;
     use tempfile::TempDir;
 
+    /// A store at `<tmp>/store` with its blobs kept in `<tmp>/blobs`
+    fn test_store(temp_dir: &TempDir) -> SkillStore {
+        let blobs = BlobStore::open(temp_dir.path().join("blobs")).unwrap();
+        SkillStore::with_blobs(temp_dir.path().join("store"), blobs).unwrap()
+    }
+
     #[test]
     fn test_calculate_hash() {
         let content = "test content";
@@ -151,14 +636,15 @@ mod tests {
     #[test]
     fn test_store_creation() {
         let temp_dir = TempDir::new().unwrap();
-        let store = SkillStore::new(temp_dir.path());
+        let blobs = BlobStore::open(temp_dir.path().join("blobs")).unwrap();
+        let store = SkillStore::with_blobs(temp_dir.path().join("store"), blobs);
         assert!(store.is_ok());
     }
 
     #[test]
     fn test_install_skill() {
         let temp_dir = TempDir::new().unwrap();
-        let store = SkillStore::new(temp_dir.path()).unwrap();
+        let store = test_store(&temp_dir);
         
         let result = store.install_skill(
             "test-skill",
@@ -169,10 +655,76 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_install_skill_directory() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = test_store(&temp_dir);
+
+        let files = vec![
+            SkillFile::new("SKILL.md", "# Skill"),
+            SkillFile::new("scripts/run.sh", "echo hi"),
+            SkillFile::new("references/api.md", "# API"),
+        ];
+        let entry = store.install_skill_files("multi", &files, "https://github.com/test/repo").unwrap();
+
+        assert_eq!(entry.files.len(), 3);
+        assert!(temp_dir.path().join("store/multi/scripts/run.sh").exists());
+        assert!(store.verify_skill("multi", &entry.hash).unwrap());
+
+        // Tampering with a supporting file is detected
+        fs::write(temp_dir.path().join("store/multi/scripts/run.sh"), "rm -rf /").unwrap();
+        assert!(!store.verify_skill("multi", &entry.hash).unwrap());
+
+        // Reinstalling drops files that no longer exist upstream
+        store.install_skill_files("multi", &files[..1], "https://github.com/test/repo").unwrap();
+        assert!(!temp_dir.path().join("store/multi/scripts").exists());
+    }
+
+    #[test]
+    fn test_single_file_tree_hash_matches_content_hash() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = test_store(&temp_dir);
+
+        let entry = store.install_skill("single", "# Single", "https://github.com/test/repo").unwrap();
+        assert_eq!(entry.hash, SkillStore::calculate_hash("# Single"));
+        assert!(entry.files.is_empty());
+        assert!(store.verify_skill("single", &entry.hash).unwrap());
+    }
+
+    #[test]
+    fn test_install_rejects_unsafe_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = test_store(&temp_dir);
+
+        let traversal = vec![
+            SkillFile::new("SKILL.md", "# Skill"),
+            SkillFile::new("../escape.sh", "echo"),
+        ];
+        assert!(store.install_skill_files("bad", &traversal, "https://github.com/test/repo").is_err());
+
+        let no_skill_md = vec![SkillFile::new("README.md", "# Readme")];
+        assert!(store.install_skill_files("bad", &no_skill_md, "https://github.com/test/repo").is_err());
+    }
+
+    #[test]
+    fn test_list_skills() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = test_store(&temp_dir);
+
+        store.install_skill("beta", "# Beta", "https://github.com/test/repo").unwrap();
+        store.install_skill("alpha", "# Alpha", "https://github.com/test/repo").unwrap();
+        fs::write(temp_dir.path().join("store/memory.json"), "{}").unwrap();
+
+        assert_eq!(store.list_skills().unwrap(), vec!["alpha", "beta"]);
+
+        store.remove_skill("beta").unwrap();
+        assert_eq!(store.list_skills().unwrap(), vec!["alpha"]);
+    }
+
     #[test]
     fn test_invalid_skill_name() {
         let temp_dir = TempDir::new().unwrap();
-        let store = SkillStore::new(temp_dir.path()).unwrap();
+        let store = test_store(&temp_dir);
         
         let result = store.install_skill(
             "../etc/passwd",
@@ -182,4 +734,95 @@ mod tests {
         
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_shared_blobs_deduplicate() {
+        let temp_dir = TempDir::new().unwrap();
+        let blobs = || BlobStore::open(temp_dir.path().join("blobs")).unwrap();
+        let project_a = SkillStore::with_blobs(temp_dir.path().join("a"), blobs()).unwrap();
+        let project_b = SkillStore::with_blobs(temp_dir.path().join("b"), blobs()).unwrap();
+
+        let entry = project_a.install_skill("memory", "# Memory", "https://github.com/one/repo").unwrap();
+        project_b.install_skill("memory", "# Memory", "https://github.com/two/repo").unwrap();
+        project_b.install_skill("other", "# Other memory", "https://github.com/two/repo").unwrap();
+
+        // Identical content is stored once, different content under its own hash
+        let blob_dir = temp_dir.path().join("blobs/sha256");
+        assert_eq!(fs::read_dir(&blob_dir).unwrap().count(), 2);
+        assert!(blob_dir.join(&entry.hash).join("SKILL.md").exists());
+        assert_eq!(
+            fs::read_to_string(project_b.get_skill_path("memory").unwrap()).unwrap(),
+            "# Memory"
+        );
+
+        // Plain directories from older stores move into the shared store
+        fs::create_dir_all(temp_dir.path().join("c/legacy")).unwrap();
+        fs::write(temp_dir.path().join("c/legacy/SKILL.md"), "# Memory").unwrap();
+        let project_c = SkillStore::with_blobs(temp_dir.path().join("c"), blobs()).unwrap();
+        assert!(project_c.share_skill("legacy").unwrap());
+        assert!(!project_c.share_skill("legacy").unwrap());
+        assert!(project_c.verify_skill("legacy", &entry.hash).unwrap());
+        assert_eq!(fs::read_dir(&blob_dir).unwrap().count(), 2);
+
+        // A damaged blob is rewritten on the next install
+        fs::write(blob_dir.join(&entry.hash).join("SKILL.md"), "tampered").unwrap();
+        assert!(!project_a.verify_skill("memory", &entry.hash).unwrap());
+        project_b.install_skill("memory", "# Memory", "https://github.com/two/repo").unwrap();
+        assert!(project_a.verify_skill("memory", &entry.hash).unwrap());
+    }
+
+    #[test]
+    fn test_gc_removes_unreferenced_blobs() {
+        let temp_dir = TempDir::new().unwrap();
+        let blobs = || BlobStore::open(temp_dir.path().join("blobs")).unwrap();
+        let project_a = SkillStore::with_blobs(temp_dir.path().join("a"), blobs()).unwrap();
+        let project_b = SkillStore::with_blobs(temp_dir.path().join("b"), blobs()).unwrap();
+
+        let shared = project_a.install_skill("shared", "# Shared", "https://github.com/test/repo").unwrap();
+        project_b.install_skill("shared", "# Shared", "https://github.com/test/repo").unwrap();
+        let old = project_a.install_skill("tool", "# Tool v1", "https://github.com/test/repo").unwrap();
+        let new = project_a.install_skill("tool", "# Tool v2", "https://github.com/test/repo").unwrap();
+
+        // Only the replaced version is unreferenced
+        let report = blobs().gc().unwrap();
+        assert_eq!(report.removed, vec![old.hash.clone()]);
+        assert_eq!(report.kept, 2);
+
+        // Blobs stay while any store links to them, even after a project is deleted
+        project_a.remove_skill("shared").unwrap();
+        fs::remove_dir_all(temp_dir.path().join("a")).unwrap();
+        let report = blobs().gc().unwrap();
+        assert_eq!(report.removed, vec![new.hash.clone()]);
+        assert!(project_b.verify_skill("shared", &shared.hash).unwrap());
+    }
+
+    #[test]
+    fn test_link_local_skill() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("src/demo");
+        fs::create_dir_all(&source).unwrap();
+        fs::write(source.join("SKILL.md"), "# Demo v1").unwrap();
+
+        let store = test_store(&temp_dir).with_linked_sources([source.clone()]);
+        let entry = store.link_local_skill("demo", &source, "file:///src/demo").unwrap();
+        assert!(entry.dev);
+        assert!(store.verify_link("demo", &source).unwrap());
+
+        // Edits to the source show through the store immediately
+        fs::write(source.join("SKILL.md"), "# Demo v2").unwrap();
+        assert_eq!(fs::read_to_string(store.get_skill_path("demo").unwrap()).unwrap(), "# Demo v2");
+
+        // Without the source among the linked directories the link is rejected
+        assert!(test_store(&temp_dir).get_skill_path("demo").is_err());
+
+        // Removing the skill only removes the link
+        store.remove_skill("demo").unwrap();
+        assert!(!store.verify_link("demo", &source).unwrap());
+        assert!(source.join("SKILL.md").exists());
+
+        // Local skills go through the same checks as downloaded ones
+        fs::write(source.join("notes.md"), "binary\0data").unwrap();
+        assert!(store.link_local_skill("demo", &source, "file:///src/demo").is_err());
+        assert!(store.link_local_skill("../demo", &source, "file:///src/demo").is_err());
+    }
 }